@@ -0,0 +1,108 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::time::{Duration, Instant};
+
+/// Writes an [asciicast v2](https://docs.asciinema.org/manual/asciicast/v2/)
+/// recording: a JSON header line describing the terminal, followed by one
+/// JSON array per captured "output" event. `DisplayManager::render_diff`
+/// hands this the exact byte buffer it writes to the real terminal each
+/// frame, so a recording reproduces the session verbatim without re-decoding
+/// the source video.
+pub struct AsciicastRecorder {
+    writer: BufWriter<File>,
+    start: Instant,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Header {
+    version: u8,
+    width: u16,
+    height: u16,
+    timestamp: u64,
+}
+
+impl AsciicastRecorder {
+    /// `width`/`height` are the terminal size in columns/rows at the start
+    /// of the session, as asciicast v2 headers expect.
+    pub fn create(path: &str, width: u16, height: u16) -> Result<Self> {
+        let file = File::create(path).with_context(|| format!("Failed to create asciicast file {}", path))?;
+        let mut writer = BufWriter::new(file);
+
+        let header = Header {
+            version: 2,
+            width,
+            height,
+            timestamp: std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or(0),
+        };
+        serde_json::to_writer(&mut writer, &header)?;
+        writer.write_all(b"\n")?;
+
+        Ok(Self { writer, start: Instant::now() })
+    }
+
+    /// Record one "output" event: `chunk` is the exact escape-sequence byte
+    /// stream written to the terminal for this frame.
+    pub fn write_event(&mut self, chunk: &[u8]) -> Result<()> {
+        if chunk.is_empty() {
+            return Ok(());
+        }
+
+        let elapsed = self.start.elapsed().as_secs_f64();
+        let text = String::from_utf8_lossy(chunk);
+        // asciicast events are `[elapsed_seconds, "o", "<chunk>"]`; a plain
+        // tuple serializes to exactly that JSON array.
+        serde_json::to_writer(&mut self.writer, &(elapsed, "o", text.as_ref()))?;
+        self.writer.write_all(b"\n")?;
+
+        Ok(())
+    }
+
+    pub fn finish(mut self) -> Result<()> {
+        self.writer.flush()?;
+        Ok(())
+    }
+}
+
+/// Stream a recorded `.cast` file back to stdout, honoring its timestamps,
+/// so a session can be replayed without touching the original video.
+pub fn play_cast(path: &str) -> Result<()> {
+    let file = File::open(path).with_context(|| format!("Failed to open asciicast file {}", path))?;
+    let reader = BufReader::new(file);
+    let mut lines = reader.lines();
+
+    let header_line = lines.next().context("Empty asciicast file (missing header)")??;
+    let _header: Header =
+        serde_json::from_str(&header_line).context("Failed to parse asciicast header")?;
+
+    let mut stdout = std::io::stdout();
+    let start = Instant::now();
+
+    for line in lines {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let (elapsed, event_type, text): (f64, String, String) =
+            serde_json::from_str(&line).context("Failed to parse asciicast event")?;
+        if event_type != "o" {
+            continue; // only "o" (output) events are replayable as screen output
+        }
+
+        let target = Duration::from_secs_f64(elapsed.max(0.0));
+        let now = start.elapsed();
+        if target > now {
+            std::thread::sleep(target - now);
+        }
+
+        stdout.write_all(text.as_bytes())?;
+        stdout.flush()?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,266 @@
+use anyhow::{Context, Result};
+use rodio::{Decoder, OutputStream, OutputStreamHandle, Sink, Source};
+use std::fs::File;
+use std::io::BufReader;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Which channel(s) of a (possibly multi-channel) audio file to route to
+/// both output speakers. Lets lecture-capture style recordings, where e.g.
+/// a lavalier mic sits on one stereo channel and room ambience on the
+/// other, pick the channel that's actually meant to be heard.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AudioChannel {
+    /// Play the file unmodified (the common stereo/mono case).
+    Stereo,
+    Left,
+    Right,
+    /// Average all input channels down to one, duplicated to both outputs.
+    Mix,
+    /// Extract a specific zero-based channel index, duplicated to both outputs.
+    Index(u16),
+}
+
+impl std::str::FromStr for AudioChannel {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "stereo" => Ok(AudioChannel::Stereo),
+            "left" => Ok(AudioChannel::Left),
+            "right" => Ok(AudioChannel::Right),
+            "mix" => Ok(AudioChannel::Mix),
+            other => other
+                .parse::<u16>()
+                .map(AudioChannel::Index)
+                .map_err(|_| format!("invalid audio channel '{}': expected left, right, mix, stereo, or a channel index", other)),
+        }
+    }
+}
+
+/// Wraps a decoded source and, for every output sample, combines its
+/// original channels down to the single channel `select` asks for before
+/// duplicating it to stereo output. Source implementations decode
+/// interleaved samples one at a time, so this buffers one input frame
+/// (`channels_in` samples) at a time to compute each output sample.
+struct ChannelSelector<S: Source<Item = i16>> {
+    inner: S,
+    channels_in: u16,
+    select: AudioChannel,
+    pending: Option<i16>,
+}
+
+impl<S: Source<Item = i16>> ChannelSelector<S> {
+    fn new(inner: S, select: AudioChannel) -> Self {
+        let channels_in = inner.channels();
+        Self { inner, channels_in, select, pending: None }
+    }
+}
+
+impl<S: Source<Item = i16>> Iterator for ChannelSelector<S> {
+    type Item = i16;
+
+    fn next(&mut self) -> Option<i16> {
+        if let Some(sample) = self.pending.take() {
+            return Some(sample);
+        }
+
+        if self.channels_in <= 1 {
+            // Nothing to select from a mono source; just duplicate it.
+            let sample = self.inner.next()?;
+            self.pending = Some(sample);
+            return Some(sample);
+        }
+
+        let mut frame = Vec::with_capacity(self.channels_in as usize);
+        for _ in 0..self.channels_in {
+            frame.push(self.inner.next()?);
+        }
+
+        let out = match self.select {
+            AudioChannel::Stereo => frame[0],
+            AudioChannel::Left => frame[0],
+            AudioChannel::Right => *frame.get(1).unwrap_or(&frame[0]),
+            AudioChannel::Mix => {
+                let sum: i32 = frame.iter().map(|&s| s as i32).sum();
+                (sum / self.channels_in as i32) as i16
+            }
+            AudioChannel::Index(idx) => *frame.get(idx as usize).unwrap_or(&frame[0]),
+        };
+
+        self.pending = Some(out);
+        Some(out)
+    }
+}
+
+impl<S: Source<Item = i16>> Source for ChannelSelector<S> {
+    fn current_frame_len(&self) -> Option<usize> {
+        self.inner
+            .current_frame_len()
+            .map(|len| (len / self.channels_in.max(1) as usize) * 2)
+    }
+
+    fn channels(&self) -> u16 {
+        2
+    }
+
+    fn sample_rate(&self) -> u32 {
+        self.inner.sample_rate()
+    }
+
+    fn total_duration(&self) -> Option<Duration> {
+        self.inner.total_duration()
+    }
+}
+
+/// Owns the audio output device and drives playback of a single track
+/// alongside the video. `_stream` must stay alive for as long as audio
+/// should play, so it lives on the struct even though it is never read;
+/// `stream_handle` is kept to create a fresh sink on every `play`/`seek`.
+pub struct AudioManager {
+    _stream: OutputStream,
+    stream_handle: OutputStreamHandle,
+    sink: Mutex<Option<Sink>>,
+    // Clock tracking: `clock_offset` is the playback position (seconds) as
+    // of the last time `origin` was reset. While playing, `origin` holds the
+    // wall-clock instant that offset corresponds to, so `get_clock()` is
+    // `clock_offset + origin.elapsed()`. While paused, `origin` is `None` and
+    // the clock is frozen at `clock_offset`.
+    clock_offset: Mutex<f64>,
+    origin: Mutex<Option<Instant>>,
+    // Remembered so `seek` can re-open and re-decode the file from the
+    // target offset instead of just relabeling the clock.
+    track: Mutex<Option<(String, AudioChannel)>>,
+}
+
+impl AudioManager {
+    pub fn new() -> Result<Self> {
+        let (stream, stream_handle) = OutputStream::try_default()
+            .context("Failed to open default audio output device")?;
+
+        Ok(Self {
+            _stream: stream,
+            stream_handle,
+            sink: Mutex::new(None),
+            clock_offset: Mutex::new(0.0),
+            origin: Mutex::new(None),
+            track: Mutex::new(None),
+        })
+    }
+
+    pub fn play(&self, path: &str, channel: AudioChannel) -> Result<()> {
+        let sink = self.open_sink(path, channel, Duration::ZERO, false)?;
+
+        *self.sink.lock().unwrap() = Some(sink);
+        *self.clock_offset.lock().unwrap() = 0.0;
+        *self.origin.lock().unwrap() = Some(Instant::now());
+        *self.track.lock().unwrap() = Some((path.to_string(), channel));
+
+        Ok(())
+    }
+
+    /// Open `path`, skip `start_at` into the decoded stream, and hand back a
+    /// fresh sink with the (optionally channel-selected) source appended but
+    /// not yet attached to `self.sink` - shared by `play` and `seek`. `paused`
+    /// pauses the sink before the source is appended, so a caller that wants
+    /// it to stay silent never races the mixer thread pulling the first
+    /// samples before `pause()` takes effect.
+    fn open_sink(&self, path: &str, channel: AudioChannel, start_at: Duration, paused: bool) -> Result<Sink> {
+        let file = File::open(path).with_context(|| format!("Failed to open audio file: {}", path))?;
+        let source = Decoder::new(BufReader::new(file))
+            .with_context(|| format!("Failed to decode audio file: {}", path))?
+            .skip_duration(start_at);
+
+        let sink = Sink::try_new(&self.stream_handle).context("Failed to create audio sink")?;
+        if paused {
+            sink.pause();
+        }
+
+        if channel == AudioChannel::Stereo {
+            sink.append(source);
+        } else {
+            sink.append(ChannelSelector::new(source, channel));
+        }
+
+        Ok(sink)
+    }
+
+    /// Current audio playback position in seconds, used as the master clock
+    /// for A/V sync. Returns `0.0` if nothing is playing yet.
+    pub fn get_clock(&self) -> f64 {
+        let offset = *self.clock_offset.lock().unwrap();
+        match *self.origin.lock().unwrap() {
+            Some(origin) => offset + origin.elapsed().as_secs_f64(),
+            None => offset,
+        }
+    }
+
+    /// Pause the sink and freeze the sync clock at its current position.
+    pub fn pause(&self) {
+        let mut origin = self.origin.lock().unwrap();
+        if let Some(o) = origin.take() {
+            *self.clock_offset.lock().unwrap() += o.elapsed().as_secs_f64();
+        }
+        if let Some(sink) = self.sink.lock().unwrap().as_ref() {
+            sink.pause();
+        }
+    }
+
+    /// Resume the sink and let the sync clock advance again.
+    pub fn resume(&self) {
+        let mut origin = self.origin.lock().unwrap();
+        if origin.is_none() {
+            *origin = Some(Instant::now());
+        }
+        if let Some(sink) = self.sink.lock().unwrap().as_ref() {
+            sink.play();
+        }
+    }
+
+    /// Re-anchor the sync clock to `seconds`, e.g. after a seek. This keeps
+    /// the reported clock consistent with the video position; it does not
+    /// re-decode the audio stream from that offset.
+    pub fn set_clock(&self, seconds: f64) {
+        *self.clock_offset.lock().unwrap() = seconds.max(0.0);
+        let mut origin = self.origin.lock().unwrap();
+        if origin.is_some() {
+            *origin = Some(Instant::now());
+        }
+    }
+
+    /// User-facing seek: actually reposition playback, not just the reported
+    /// clock. `rodio::Sink` has no seek of its own, so this re-opens the
+    /// track, decodes forward to `seconds`, and swaps in a fresh sink built
+    /// from there - the old sink (and whatever audio it had buffered) is
+    /// dropped. Falls back to a clock-only re-anchor (same as `set_clock`)
+    /// if there's no remembered track to re-open, or if re-decoding it
+    /// fails; either way the reported clock still lines up with the video.
+    ///
+    /// Decoding forward from the start of the file costs more the further
+    /// into the track `seconds` is - noticeable if called on every
+    /// frame-step keystroke deep into a long track - but `rodio`'s decoders
+    /// have no index-based seek to fall back to, and getting the audio
+    /// output to actually land on the right sample takes priority over
+    /// keeping every single step instant.
+    pub fn seek(&self, seconds: f64) {
+        let seconds = seconds.max(0.0);
+        let was_playing = self.origin.lock().unwrap().is_some();
+
+        let track = self.track.lock().unwrap().clone();
+        let Some((path, channel)) = track else {
+            self.set_clock(seconds);
+            return;
+        };
+
+        match self.open_sink(&path, channel, Duration::from_secs_f64(seconds), !was_playing) {
+            Ok(sink) => {
+                *self.sink.lock().unwrap() = Some(sink);
+            }
+            Err(e) => {
+                eprintln!("⚠️  Audio seek failed, clock will drift out of sync: {}", e);
+            }
+        }
+
+        self.set_clock(seconds);
+    }
+}
@@ -1,4 +1,4 @@
-use anyhow::Result;
+use anyhow::{Context, Result};
 use crossterm::{
     cursor,
     style::Print,
@@ -8,20 +8,198 @@ use crossterm::{
 };
 use std::io::{Stdout, Write};
 
+use crate::core::palette::Palette;
+
 #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, clap::ValueEnum)]
 pub enum DisplayMode {
     Ascii,
     Rgb,
+    /// Indexed ANSI 256-color output (`\x1b[38;5;{n}m`) instead of 24-bit
+    /// truecolor. Works on terminals without truecolor support and halves
+    /// the color escape-sequence size per cell.
+    Ansi256,
+    /// Explicit alias for the upper-half-block (`▀`) truecolor rendering
+    /// `core::processor::FrameProcessor` already builds every cell with:
+    /// each cell packs two vertically-stacked source pixels (fg = top, bg =
+    /// bottom), doubling effective vertical resolution versus one pixel per
+    /// cell. Renders identically to `Rgb`; picking it just makes that
+    /// doubling explicit at the CLI instead of an `Rgb`-mode implementation
+    /// detail.
+    HalfBlock,
+    /// Request full 24-bit color without having to know whether the
+    /// terminal actually supports it: resolves to `Rgb` if `$COLORTERM`
+    /// advertises truecolor, otherwise downgrades to `Ansi256`. See
+    /// `resolve_display_mode`.
+    TrueColor,
+}
+
+/// Downgrade `TrueColor` to `Rgb` or `Ansi256` depending on whether the
+/// terminal advertises 24-bit support via `$COLORTERM` (`truecolor`/`24bit`,
+/// the same values ffmpeg, tmux, and most other terminal-aware tools check).
+/// Every other mode passes through unchanged. Called once, up front, so
+/// nothing downstream (`DisplayManager`, the adaptive render-mode match in
+/// `core::player::play_realtime`) ever has to handle `TrueColor` itself.
+pub fn resolve_display_mode(requested: DisplayMode) -> DisplayMode {
+    if requested != DisplayMode::TrueColor {
+        return requested;
+    }
+
+    let colorterm = std::env::var("COLORTERM").unwrap_or_default();
+    if colorterm.eq_ignore_ascii_case("truecolor") || colorterm.eq_ignore_ascii_case("24bit") {
+        DisplayMode::Rgb
+    } else {
+        DisplayMode::Ansi256
+    }
+}
+
+/// Which dithering algorithm, if any, `DisplayManager::render_diff` applies
+/// to cells before quantizing them down to `Ansi256`/`Ascii` (see
+/// `DisplayManager::dither_cells`). No effect in `Rgb`/`HalfBlock`/
+/// `TrueColor` modes, which have no quantization step to dither against.
+#[derive(Copy, Clone, PartialEq, Eq, Debug, clap::ValueEnum)]
+pub enum DitherMode {
+    /// Quantize straight to the nearest color; no dithering.
+    None,
+    /// 8x8 Bayer ordered dithering: cheap, parallelizes trivially (each
+    /// cell's bias only depends on its own position), but leaves visible
+    /// cross-hatch texture on gradients.
+    Ordered,
+    /// Floyd-Steinberg error diffusion: smoother gradients, at the cost of
+    /// being inherently sequential (each cell's diffused error feeds the
+    /// next).
+    ErrorDiffusion,
+}
+
+// 8x8 Bayer matrix (values 0..63) used by `DitherMode::Ordered` to bias each
+// cell's color by a fixed, position-dependent amount before quantizing.
+const BAYER_8X8: [u8; 64] = [
+    0, 32, 8, 40, 2, 34, 10, 42, 48, 16, 56, 24, 50, 18, 58, 26, 12, 44, 4, 36, 14, 46, 6, 38, 60, 28, 52, 20, 62, 30,
+    54, 22, 3, 35, 11, 43, 1, 33, 9, 41, 51, 19, 59, 27, 49, 17, 57, 25, 15, 47, 7, 39, 13, 45, 5, 37, 63, 31, 55, 23,
+    61, 29, 53, 21,
+];
+
+/// `BAYER_8X8`'s threshold for `(x, y)`, centered on zero and scaled to a
+/// channel-value bias roughly comparable to one quantization step.
+fn ordered_bias(x: usize, y: usize) -> f32 {
+    let threshold = BAYER_8X8[(y % 8) * 8 + (x % 8)] as f32;
+    (threshold / 63.0 - 0.5) * 32.0
+}
+
+// ASCII brightness ramp, darkest to brightest. Matches the ramp the
+// (currently unwired) `renderer::display` module uses, so the two stay in
+// sync if ASCII live playback is ever turned back on.
+const ASCII_CHARS: &[char] = &[' ', '.', ':', '-', '=', '+', '*', '#', '%', '@'];
+
+// Partial-block glyphs at 1/8-column resolution (index = eighths filled),
+// so the OSD progress bar's leading edge moves smoothly instead of jumping
+// a whole column at a time.
+const PROGRESS_CHARS: &[char] = &[' ', '▏', '▎', '▍', '▌', '▋', '▊', '▉', '█'];
+
+/// Render a `width`-column `[...]`-bracketed progress bar for `fraction`
+/// (clamped to `0.0..=1.0`), using `PROGRESS_CHARS` for sub-column
+/// precision on the bar's leading edge.
+fn render_progress_bar(fraction: f64, width: usize) -> String {
+    if width == 0 {
+        return String::new();
+    }
+    let fraction = fraction.clamp(0.0, 1.0);
+    let total_eighths = (fraction * (width * 8) as f64).round() as usize;
+    let full_cols = (total_eighths / 8).min(width);
+    let remainder = if full_cols < width { total_eighths % 8 } else { 0 };
+
+    let mut bar = String::with_capacity(width + 2);
+    bar.push('[');
+    for i in 0..width {
+        bar.push(if i < full_cols {
+            PROGRESS_CHARS[8]
+        } else if i == full_cols && remainder > 0 {
+            PROGRESS_CHARS[remainder]
+        } else {
+            PROGRESS_CHARS[0]
+        });
+    }
+    bar.push(']');
+    bar
+}
+
+fn clamp_rgb(c: (f32, f32, f32)) -> (u8, u8, u8) {
+    (c.0.clamp(0.0, 255.0) as u8, c.1.clamp(0.0, 255.0) as u8, c.2.clamp(0.0, 255.0) as u8)
+}
+
+fn sub_rgb(a: (u8, u8, u8), b: (u8, u8, u8)) -> (f32, f32, f32) {
+    (a.0 as f32 - b.0 as f32, a.1 as f32 - b.1 as f32, a.2 as f32 - b.2 as f32)
+}
+
+/// Spread a Floyd-Steinberg error term (7/16 right, 3/16 below-left,
+/// 5/16 below, 1/16 below-right) onto not-yet-visited neighbors of `(x, y)`
+/// in `acc`. When scanning right-to-left, "right" and "left" are mirrored so
+/// the diffusion always lands ahead of the scan direction.
+fn diffuse_rgb(
+    acc: &mut [(f32, f32, f32)],
+    width: usize,
+    height: usize,
+    x: usize,
+    y: usize,
+    right_to_left: bool,
+    err: (f32, f32, f32),
+) {
+    let ahead = if right_to_left { x.checked_sub(1) } else { Some(x + 1).filter(|&v| v < width) };
+    let behind = if right_to_left { Some(x + 1).filter(|&v| v < width) } else { x.checked_sub(1) };
+
+    let add = |acc: &mut [(f32, f32, f32)], idx: usize, weight: f32| {
+        acc[idx].0 += err.0 * weight;
+        acc[idx].1 += err.1 * weight;
+        acc[idx].2 += err.2 * weight;
+    };
+
+    if let Some(ax) = ahead {
+        add(acc, y * width + ax, 7.0 / 16.0);
+    }
+    if y + 1 < height {
+        if let Some(bx) = behind {
+            add(acc, (y + 1) * width + bx, 3.0 / 16.0);
+        }
+        add(acc, (y + 1) * width + x, 5.0 / 16.0);
+        if let Some(ax) = ahead {
+            add(acc, (y + 1) * width + ax, 1.0 / 16.0);
+        }
+    }
+}
+
+/// Scalar-value counterpart of `diffuse_rgb`, used for ASCII brightness
+/// diffusion.
+fn diffuse_scalar(acc: &mut [f32], width: usize, height: usize, x: usize, y: usize, right_to_left: bool, err: f32) {
+    let ahead = if right_to_left { x.checked_sub(1) } else { Some(x + 1).filter(|&v| v < width) };
+    let behind = if right_to_left { Some(x + 1).filter(|&v| v < width) } else { x.checked_sub(1) };
+
+    if let Some(ax) = ahead {
+        acc[y * width + ax] += err * 7.0 / 16.0;
+    }
+    if y + 1 < height {
+        if let Some(bx) = behind {
+            acc[(y + 1) * width + bx] += err * 3.0 / 16.0;
+        }
+        acc[(y + 1) * width + x] += err * 5.0 / 16.0;
+        if let Some(ax) = ahead {
+            acc[(y + 1) * width + ax] += err * 1.0 / 16.0;
+        }
+    }
 }
 
 pub struct DisplayManager {
     stdout: Stdout,
     mode: DisplayMode,
     last_cells: Option<Vec<crate::core::processor::CellData>>,
+    dither: DitherMode,
+    serpentine: bool,
+    asciicast: Option<crate::core::asciicast::AsciicastRecorder>,
+    cell_record_path: Option<String>,
+    cell_recorder: Option<crate::core::replay::CellStreamWriter<std::fs::File>>,
 }
 
 impl DisplayManager {
     pub fn new(mode: DisplayMode) -> Result<Self> {
+        let mode = resolve_display_mode(mode);
         let mut stdout = std::io::stdout();
         terminal::enable_raw_mode()?;
         stdout.execute(EnterAlternateScreen)?;
@@ -45,39 +223,335 @@ impl DisplayManager {
             stdout,
             mode,
             last_cells: None,
+            dither: DitherMode::None,
+            serpentine: false,
+            asciicast: None,
+            cell_record_path: None,
+            cell_recorder: None,
         })
     }
 
+    /// Select which dithering algorithm, if any, to apply to cells before
+    /// they're quantized to ANSI256 indices or ASCII glyphs, to smooth out
+    /// banding on gradients. `DitherMode::None` by default, so the
+    /// zero-allocation diff path stays the common case.
+    pub fn with_dither(mut self, dither: DitherMode) -> Self {
+        self.dither = dither;
+        self
+    }
+
+    /// When dithering, alternate scan direction every other row to avoid
+    /// the directional streaking a pure left-to-right scan produces.
+    pub fn with_serpentine(mut self, serpentine: bool) -> Self {
+        self.serpentine = serpentine;
+        self
+    }
+
+    /// If `path` is given, capture every `render_diff` frame's raw byte
+    /// stream into an asciicast v2 recording at that path, replayable later
+    /// with `asciicast::play_cast` without re-decoding the source video.
+    pub fn with_record_cast(mut self, path: Option<&str>) -> Result<Self> {
+        if let Some(path) = path {
+            let (cols, rows) = terminal::size().unwrap_or((80, 24));
+            self.asciicast = Some(crate::core::asciicast::AsciicastRecorder::create(path, cols, rows)?);
+        }
+        Ok(self)
+    }
+
+    /// If `path` is given, capture every `render_diff` frame's cell grid
+    /// into a `core::replay` QOI-style delta stream at that path, replayable
+    /// later with `replay::CellStreamReader` without re-decoding the source
+    /// video or re-rendering any escape sequences. The stream isn't opened
+    /// until the first `render_diff` call, since the cell grid's dimensions
+    /// (needed by `CellStreamWriter::new`) aren't known until then.
+    pub fn with_record_cells(mut self, path: Option<&str>) -> Self {
+        self.cell_record_path = path.map(String::from);
+        self
+    }
+
+    /// Finish and flush the asciicast/cell-replay recordings, if either is
+    /// in progress.
+    pub fn finish_recording(&mut self) -> Result<()> {
+        if let Some(recorder) = self.asciicast.take() {
+            recorder.finish()?;
+        }
+        if let Some(recorder) = self.cell_recorder.take() {
+            recorder.finish()?;
+        }
+        Ok(())
+    }
 
     pub fn render_frame(&mut self, _frame_data: &[u8]) -> Result<()> {
         // Legacy method, no longer used.
         Ok(())
     }
 
+    /// Render a raw RGB frame straight to the terminal via a pixel graphics
+    /// protocol, bypassing `render_diff`'s cell grid entirely. Unlike
+    /// `render_diff`, there's no previous-frame diffing here: each call is a
+    /// full-frame transmit-and-display, since Kitty/Sixel have no per-cell
+    /// diff concept to exploit. `rgb` must be `width * height * 3` bytes.
+    pub fn render_pixels(
+        &mut self,
+        rgb: &[u8],
+        width: u32,
+        height: u32,
+        target: crate::core::render_target::RenderTarget,
+    ) -> Result<()> {
+        let payload = match target {
+            crate::core::render_target::RenderTarget::Kitty => {
+                crate::core::render_target::render_kitty(rgb, width, height)
+            }
+            crate::core::render_target::RenderTarget::Sixel => {
+                crate::core::render_target::render_sixel(rgb, width as usize, height as usize)
+            }
+            crate::core::render_target::RenderTarget::HalfBlock | crate::core::render_target::RenderTarget::Auto => {
+                anyhow::bail!("render_pixels requires a resolved Kitty or Sixel target")
+            }
+        };
+
+        self.stdout.queue(cursor::MoveTo(0, 0))?;
+        self.stdout.write_all(&payload)?;
+        self.stdout.flush()?;
+        Ok(())
+    }
+
+    /// Invalidate the cached previous-cell buffer so the next `render_diff`
+    /// call does a clean full repaint. Used after a detected scene cut to
+    /// avoid diff artifacts bleeding across the cut.
+    pub fn force_full_redraw(&mut self) {
+        self.last_cells = None;
+    }
+
+    // Draws a single-line overlay on the reserved bottom row (current /
+    // total timecode, FPS, a progress bar, etc). Writes directly to the
+    // terminal without touching `last_cells`, so it never corrupts the diff
+    // renderer's cache: the next `render_diff` call simply overwrites
+    // whatever it needs to in that row based on its own cached state,
+    // independent of the OSD text.
+    pub fn render_osd(&mut self, text: &str, fraction: f64) -> Result<()> {
+        let (term_cols, term_rows) = terminal::size().unwrap_or((80, 24));
+        if term_rows == 0 {
+            return Ok(());
+        }
+        let osd_row = term_rows - 1;
+
+        let mut line = text.to_string();
+        // Room for a space plus the bar's own `[...]` brackets; anything
+        // narrower and the bar would be too squeezed to read.
+        let bar_width = (term_cols as usize).saturating_sub(line.chars().count() + 3);
+        if bar_width > 0 {
+            line.push(' ');
+            line.push_str(&render_progress_bar(fraction, bar_width));
+        }
+
+        let display_width = line.chars().count();
+        if display_width > term_cols as usize {
+            line = line.chars().take(term_cols as usize).collect();
+        }
+
+        self.stdout.queue(cursor::MoveTo(0, osd_row))?;
+        self.stdout.queue(crossterm::terminal::Clear(crossterm::terminal::ClearType::CurrentLine))?;
+        self.stdout.queue(Print(&line))?;
+        self.stdout.flush()?;
+
+        Ok(())
+    }
+
+    /// Smooth out quantization banding by biasing each cell's color/
+    /// brightness before it's quantized to ANSI256 indices or ASCII glyphs.
+    /// Two algorithms, selected by `self.dither`:
+    /// - `ErrorDiffusion`: Floyd-Steinberg, diffusing each cell's
+    ///   quantization error onto not-yet-visited neighbors (7/16 right,
+    ///   3/16 below-left, 5/16 below, 1/16 below-right) - smoother
+    ///   gradients, but inherently sequential per scanline.
+    /// - `Ordered`: an 8x8 Bayer matrix biases each cell by a fixed,
+    ///   position-dependent amount with no dependency between cells -
+    ///   cheaper, at the cost of visible cross-hatch texture on gradients.
+    /// Never called with `DitherMode::None` (see `render_diff`'s gate).
+    fn dither_cells(
+        &self,
+        cells: &[crate::core::processor::CellData],
+        width: usize,
+        palette: Option<&Palette>,
+    ) -> Vec<crate::core::processor::CellData> {
+        let height = if width == 0 { 0 } else { cells.len() / width };
+        let mut out = cells.to_vec();
+
+        match self.mode {
+            DisplayMode::Ansi256 => {
+                let palette = palette.expect("Ansi256 dithering requires a per-frame palette");
+                match self.dither {
+                    DitherMode::ErrorDiffusion => {
+                        // fg and bg are dithered as two independent RGB planes.
+                        let mut fg_acc: Vec<(f32, f32, f32)> =
+                            cells.iter().map(|c| (c.fg.0 as f32, c.fg.1 as f32, c.fg.2 as f32)).collect();
+                        let mut bg_acc: Vec<(f32, f32, f32)> =
+                            cells.iter().map(|c| (c.bg.0 as f32, c.bg.1 as f32, c.bg.2 as f32)).collect();
+
+                        for y in 0..height {
+                            let right_to_left = self.serpentine && y % 2 == 1;
+                            for i in 0..width {
+                                let x = if right_to_left { width - 1 - i } else { i };
+                                let idx = y * width + x;
+
+                                let fg_in = clamp_rgb(fg_acc[idx]);
+                                let bg_in = clamp_rgb(bg_acc[idx]);
+                                let fg_idx = palette.quantize(fg_in.0, fg_in.1, fg_in.2);
+                                let bg_idx = palette.quantize(bg_in.0, bg_in.1, bg_in.2);
+                                let fg_chosen = palette.color_at(fg_idx);
+                                let bg_chosen = palette.color_at(bg_idx);
+
+                                out[idx].fg = fg_chosen;
+                                out[idx].bg = bg_chosen;
+
+                                let fg_err = sub_rgb(fg_in, fg_chosen);
+                                let bg_err = sub_rgb(bg_in, bg_chosen);
+                                diffuse_rgb(&mut fg_acc, width, height, x, y, right_to_left, fg_err);
+                                diffuse_rgb(&mut bg_acc, width, height, x, y, right_to_left, bg_err);
+                            }
+                        }
+                    }
+                    DitherMode::Ordered => {
+                        for y in 0..height {
+                            for x in 0..width {
+                                let idx = y * width + x;
+                                let bias = ordered_bias(x, y);
+
+                                let fg_in = clamp_rgb((
+                                    cells[idx].fg.0 as f32 + bias,
+                                    cells[idx].fg.1 as f32 + bias,
+                                    cells[idx].fg.2 as f32 + bias,
+                                ));
+                                let bg_in = clamp_rgb((
+                                    cells[idx].bg.0 as f32 + bias,
+                                    cells[idx].bg.1 as f32 + bias,
+                                    cells[idx].bg.2 as f32 + bias,
+                                ));
+
+                                out[idx].fg = palette.color_at(palette.quantize(fg_in.0, fg_in.1, fg_in.2));
+                                out[idx].bg = palette.color_at(palette.quantize(bg_in.0, bg_in.1, bg_in.2));
+                            }
+                        }
+                    }
+                    DitherMode::None => unreachable!("render_diff only dithers when self.dither != DitherMode::None"),
+                }
+            }
+            DisplayMode::Ascii => match self.dither {
+                DitherMode::ErrorDiffusion => {
+                    let mut brightness: Vec<f32> = cells
+                        .iter()
+                        .map(|c| (c.fg.0 as u32 * 299 + c.fg.1 as u32 * 587 + c.fg.2 as u32 * 114) as f32 / 1000.0)
+                        .collect();
+
+                    for y in 0..height {
+                        let right_to_left = self.serpentine && y % 2 == 1;
+                        for i in 0..width {
+                            let x = if right_to_left { width - 1 - i } else { i };
+                            let idx = y * width + x;
+
+                            let value = brightness[idx].clamp(0.0, 255.0);
+                            let levels = ASCII_CHARS.len() as u32 - 1;
+                            let char_idx = ((value as u32 * levels) / 255) as usize;
+                            let chosen_level = (char_idx as u32 * 255) / levels;
+
+                            out[idx].char = ASCII_CHARS[char_idx];
+
+                            let err = value - chosen_level as f32;
+                            diffuse_scalar(&mut brightness, width, height, x, y, right_to_left, err);
+                        }
+                    }
+                }
+                DitherMode::Ordered => {
+                    for y in 0..height {
+                        for x in 0..width {
+                            let idx = y * width + x;
+                            let cell = &cells[idx];
+                            let brightness =
+                                (cell.fg.0 as u32 * 299 + cell.fg.1 as u32 * 587 + cell.fg.2 as u32 * 114) as f32
+                                    / 1000.0;
+                            let value = (brightness + ordered_bias(x, y)).clamp(0.0, 255.0);
+                            let levels = ASCII_CHARS.len() as u32 - 1;
+                            let char_idx = ((value as u32 * levels) / 255) as usize;
+                            out[idx].char = ASCII_CHARS[char_idx];
+                        }
+                    }
+                }
+                DitherMode::None => unreachable!("render_diff only dithers when self.dither != DitherMode::None"),
+            },
+            DisplayMode::Rgb | DisplayMode::HalfBlock | DisplayMode::TrueColor => {
+                // Truecolor output has no quantization step to dither
+                // against; nothing to do.
+            }
+        }
+
+        out
+    }
+
     // Optimized Diffing Renderer
     // Takes a grid of CellData (calculated by Processor) and updates the terminal.
     pub fn render_diff(&mut self, cells: &[crate::core::processor::CellData], width: usize) -> Result<()> {
-        // VSync Begin
-        self.stdout.queue(Print("\x1b[?2026h"))?;
+        // In Ansi256 mode, build this frame's median-cut palette from every
+        // cell's fg/bg color before anything else touches the cells, since
+        // both dithering and the diff loop below need to quantize against it.
+        let palette = (self.mode == DisplayMode::Ansi256)
+            .then(|| Palette::build(cells.iter().flat_map(|c| [c.fg, c.bg])));
+
+        let dithered;
+        let cells: &[crate::core::processor::CellData] =
+            if self.dither != DitherMode::None && self.mode != DisplayMode::Rgb && self.mode != DisplayMode::HalfBlock {
+                dithered = self.dither_cells(cells, width, palette.as_ref());
+                &dithered
+            } else {
+                cells
+            };
 
         let mut force_redraw = false;
         if self.last_cells.is_none() || self.last_cells.as_ref().unwrap().len() != cells.len() {
-            self.stdout.queue(crossterm::terminal::Clear(crossterm::terminal::ClearType::All))?;
             self.last_cells = Some(vec![crate::core::processor::CellData { char: ' ', fg: (0,0,0), bg: (0,0,0) }; cells.len()]);
             force_redraw = true;
         }
 
+        if self.cell_recorder.is_none() {
+            if let Some(path) = self.cell_record_path.take() {
+                let file = std::fs::File::create(&path)
+                    .with_context(|| format!("Failed to create cell replay file {}", path))?;
+                let height = if width == 0 { 0 } else { cells.len() / width };
+                self.cell_recorder = Some(crate::core::replay::CellStreamWriter::new(file, width, height)?);
+            }
+        }
+
         let last_cells = self.last_cells.as_mut().unwrap();
-        
+
         // OPTIMIZATION: Pre-allocate buffer with a more accurate size estimate
         // Each cell update takes approx 15-20 bytes (cursor move + color + char)
         // If full redraw, size is large. If diff, size is small.
         // We use a safe upper bound estimate to avoid reallocations.
         let estimated_size = if force_redraw { cells.len() * 20 } else { cells.len() * 5 };
-        let mut buffer = Vec::with_capacity(estimated_size);
-        
+        // Every byte this frame writes to the terminal - VSync begin, the
+        // palette redefinition, an optional full clear, the cell diff, and
+        // VSync end - is assembled into this one buffer so it can be
+        // written verbatim to both stdout and (if recording) the asciicast
+        // writer below.
+        let mut buffer = Vec::with_capacity(estimated_size + 32);
+        buffer.extend_from_slice(b"\x1b[?2026h"); // VSync Begin
+
+        // Redefine the terminal's 256-color palette to this frame's colors
+        // before any `\x1b[38/48;5;{i}m` reference below resolves against it.
+        if let Some(palette) = palette.as_ref() {
+            buffer.extend_from_slice(palette.osc4_sequence().as_bytes());
+        }
+
+        if force_redraw {
+            buffer.extend_from_slice(b"\x1b[2J");
+        }
+
         let mut last_fg: Option<(u8, u8, u8)> = None;
         let mut last_bg: Option<(u8, u8, u8)> = None;
+        // Only used in `DisplayMode::Ansi256`, to dedup repeated quantized
+        // indices the same way `last_fg`/`last_bg` dedup truecolor values.
+        let mut last_fg_idx: Option<u8> = None;
+        let mut last_bg_idx: Option<u8> = None;
         
         // Calculate centering offsets dynamically
         let (term_cols, term_rows) = terminal::size().unwrap_or((80, 24));
@@ -121,26 +595,45 @@ impl DisplayManager {
                 }
                 
                 // Color updates
-                if Some(cell.fg) != last_fg { 
-                    // OPTIMIZATION: Direct byte pushing for colors
-                    buffer.extend_from_slice(b"\x1b[38;2;");
-                    buffer.extend_from_slice(cell.fg.0.to_string().as_bytes());
-                    buffer.extend_from_slice(b";");
-                    buffer.extend_from_slice(cell.fg.1.to_string().as_bytes());
-                    buffer.extend_from_slice(b";");
-                    buffer.extend_from_slice(cell.fg.2.to_string().as_bytes());
-                    buffer.extend_from_slice(b"m");
-                    last_fg = Some(cell.fg); 
-                }
-                if Some(cell.bg) != last_bg { 
-                    buffer.extend_from_slice(b"\x1b[48;2;");
-                    buffer.extend_from_slice(cell.bg.0.to_string().as_bytes());
-                    buffer.extend_from_slice(b";");
-                    buffer.extend_from_slice(cell.bg.1.to_string().as_bytes());
-                    buffer.extend_from_slice(b";");
-                    buffer.extend_from_slice(cell.bg.2.to_string().as_bytes());
-                    buffer.extend_from_slice(b"m");
-                    last_bg = Some(cell.bg); 
+                if self.mode == DisplayMode::Ansi256 {
+                    let palette = palette.as_ref().expect("Ansi256 render_diff always builds a palette");
+                    let fg_idx = palette.quantize(cell.fg.0, cell.fg.1, cell.fg.2);
+                    let bg_idx = palette.quantize(cell.bg.0, cell.bg.1, cell.bg.2);
+
+                    if Some(fg_idx) != last_fg_idx {
+                        buffer.extend_from_slice(b"\x1b[38;5;");
+                        buffer.extend_from_slice(fg_idx.to_string().as_bytes());
+                        buffer.extend_from_slice(b"m");
+                        last_fg_idx = Some(fg_idx);
+                    }
+                    if Some(bg_idx) != last_bg_idx {
+                        buffer.extend_from_slice(b"\x1b[48;5;");
+                        buffer.extend_from_slice(bg_idx.to_string().as_bytes());
+                        buffer.extend_from_slice(b"m");
+                        last_bg_idx = Some(bg_idx);
+                    }
+                } else {
+                    if Some(cell.fg) != last_fg {
+                        // OPTIMIZATION: Direct byte pushing for colors
+                        buffer.extend_from_slice(b"\x1b[38;2;");
+                        buffer.extend_from_slice(cell.fg.0.to_string().as_bytes());
+                        buffer.extend_from_slice(b";");
+                        buffer.extend_from_slice(cell.fg.1.to_string().as_bytes());
+                        buffer.extend_from_slice(b";");
+                        buffer.extend_from_slice(cell.fg.2.to_string().as_bytes());
+                        buffer.extend_from_slice(b"m");
+                        last_fg = Some(cell.fg);
+                    }
+                    if Some(cell.bg) != last_bg {
+                        buffer.extend_from_slice(b"\x1b[48;2;");
+                        buffer.extend_from_slice(cell.bg.0.to_string().as_bytes());
+                        buffer.extend_from_slice(b";");
+                        buffer.extend_from_slice(cell.bg.1.to_string().as_bytes());
+                        buffer.extend_from_slice(b";");
+                        buffer.extend_from_slice(cell.bg.2.to_string().as_bytes());
+                        buffer.extend_from_slice(b"m");
+                        last_bg = Some(cell.bg);
+                    }
                 }
                 
                 // Write character
@@ -158,13 +651,18 @@ impl DisplayManager {
         }
 
         buffer.extend_from_slice(b"\x1b[0m");
+        buffer.extend_from_slice(b"\x1b[?2026l"); // VSync End
+
+        if let Some(recorder) = self.asciicast.as_mut() {
+            recorder.write_event(&buffer)?;
+        }
+        if let Some(recorder) = self.cell_recorder.as_mut() {
+            recorder.push_frame(cells)?;
+        }
+
         self.stdout.write_all(&buffer)?;
         self.stdout.flush()?;
-        
-        // End VSync AFTER flush to ensure complete frame is ready
-        self.stdout.queue(Print("\x1b[?2026l"))?;
-        self.stdout.flush()?;
-        
+
         Ok(())
     }
 }
@@ -9,3 +9,165 @@ pub fn extract_frames(_input: &str, _output_dir: &str, _width: u32, _height: u32
     // Please use OpenCV VideoDecoder for video playback
     unimplemented!("Use OpenCV VideoDecoder for video decoding instead")
 }
+
+use opencv::{prelude::*, videoio, imgproc, core as cvcore};
+use std::fs::File;
+use std::io::Write;
+
+use crate::core::display_manager::DisplayMode;
+use crate::core::processor::{CellData, FrameProcessor};
+
+// Mirrors `core::display_manager::ASCII_CHARS`; duplicated rather than made
+// `pub` there since the two renderers (live terminal vs. exported script)
+// aren't meant to share state, just the same visual ramp.
+const ASCII_CHARS: &[char] = &[' ', '.', ':', '-', '=', '+', '*', '#', '%', '@'];
+
+/// Walk `input` frame by frame with OpenCV, render each through the same
+/// `FrameProcessor` the live player uses, and write a single self-contained
+/// Bash script to `output` that replays the animation with nothing but
+/// `echo`/`sleep` - no dependency on this binary, OpenCV, or ffmpeg.
+pub fn render_script(
+    input: &str,
+    output: &str,
+    width: u32,
+    height: u32,
+    fps: u32,
+    mode: DisplayMode,
+) -> Result<()> {
+    if matches!(mode, DisplayMode::Ansi256) {
+        anyhow::bail!("render only supports Rgb/HalfBlock or Ascii display modes (no indexed-color escape form makes sense in a portable script)");
+    }
+
+    let mut capture = videoio::VideoCapture::from_file(input, videoio::CAP_ANY)
+        .with_context(|| format!("Failed to open video file: {}", input))?;
+    if !capture.is_opened()? {
+        anyhow::bail!("Failed to open video file: {}", input);
+    }
+
+    let total_frames = capture.get(videoio::CAP_PROP_FRAME_COUNT)?.max(0.0) as u64;
+
+    // Half-block packs two source rows into one terminal row (fg = top
+    // pixel, bg = bottom pixel), so the pixel canvas is twice as tall as the
+    // requested terminal row count.
+    let pixel_w = width as i32;
+    let pixel_h = (height * 2) as i32;
+    let processor = FrameProcessor::new(pixel_w as usize, pixel_h as usize);
+
+    let mut script = File::create(output).with_context(|| format!("Failed to create {}", output))?;
+    writeln!(script, "#!/usr/bin/env bash")?;
+    writeln!(script, "echo -en '\\033[2J'")?;
+
+    let sleep_secs = 1.0 / fps.max(1) as f64;
+    let mut frame = Mat::default();
+    let mut frame_idx: u64 = 0;
+
+    loop {
+        if !capture.read(&mut frame)? || frame.empty() {
+            break;
+        }
+
+        let mut resized = Mat::default();
+        imgproc::resize(
+            &frame,
+            &mut resized,
+            cvcore::Size::new(pixel_w, pixel_h),
+            0.0,
+            0.0,
+            imgproc::INTER_AREA,
+        )?;
+
+        let mut rgb = Mat::default();
+        imgproc::cvt_color(&resized, &mut rgb, imgproc::COLOR_BGR2RGB, 0, cvcore::AlgorithmHint::ALGO_HINT_DEFAULT)?;
+        if !rgb.is_continuous() {
+            anyhow::bail!("Frame data is not continuous");
+        }
+
+        let cells = processor.process_frame(rgb.data_bytes()?);
+        let payload = match mode {
+            DisplayMode::Ascii => render_payload_ascii(&cells, pixel_w as usize),
+            _ => render_payload_rgb(&cells, pixel_w as usize),
+        };
+
+        writeln!(script, "echo -en '\\033[H{}'", payload)?;
+        writeln!(script, "sleep {:.6}", sleep_secs)?;
+
+        frame_idx += 1;
+        print_progress(frame_idx, total_frames);
+    }
+    eprintln!();
+
+    drop(script);
+    make_executable(output)?;
+
+    Ok(())
+}
+
+/// Render one frame's cells as 24-bit SGR escape sequences plus the
+/// half-block glyph, deduping repeated fg/bg the same way the live
+/// `DisplayManager::render_diff` avoids redundant color codes.
+fn render_payload_rgb(cells: &[CellData], width: usize) -> String {
+    let mut out = String::with_capacity(cells.len() * 16);
+    let mut last_fg: Option<(u8, u8, u8)> = None;
+    let mut last_bg: Option<(u8, u8, u8)> = None;
+
+    for (i, cell) in cells.iter().enumerate() {
+        if i > 0 && i % width == 0 {
+            out.push_str("\\n");
+        }
+        if Some(cell.fg) != last_fg {
+            out.push_str(&format!("\\033[38;2;{};{};{}m", cell.fg.0, cell.fg.1, cell.fg.2));
+            last_fg = Some(cell.fg);
+        }
+        if Some(cell.bg) != last_bg {
+            out.push_str(&format!("\\033[48;2;{};{};{}m", cell.bg.0, cell.bg.1, cell.bg.2));
+            last_bg = Some(cell.bg);
+        }
+        out.push(cell.char);
+    }
+    out.push_str("\\033[0m");
+    out
+}
+
+/// Render one frame's cells as plain ASCII brightness art (fg channel only,
+/// same luma weights `DisplayManager::dither_cells` uses for Ascii mode).
+fn render_payload_ascii(cells: &[CellData], width: usize) -> String {
+    let mut out = String::with_capacity(cells.len() + cells.len() / width);
+    let levels = ASCII_CHARS.len() as u32 - 1;
+
+    for (i, cell) in cells.iter().enumerate() {
+        if i > 0 && i % width == 0 {
+            out.push_str("\\n");
+        }
+        let brightness = (cell.fg.0 as u32 * 299 + cell.fg.1 as u32 * 587 + cell.fg.2 as u32 * 114) / 1000;
+        let char_idx = ((brightness * levels) / 255) as usize;
+        out.push(ASCII_CHARS[char_idx]);
+    }
+    out
+}
+
+fn print_progress(current: u64, total: u64) {
+    if total == 0 {
+        eprint!("\r🎬 Rendering frame {}...", current);
+        let _ = std::io::stderr().flush();
+        return;
+    }
+    let pct = ((current as f64 / total as f64) * 100.0).min(100.0);
+    let filled = ((pct / 100.0) * 30.0).round() as usize;
+    let bar = "#".repeat(filled) + &"-".repeat(30 - filled);
+    eprint!("\r🎬 Rendering [{}] {:>3}% ({}/{})", bar, pct as u32, current, total);
+    let _ = std::io::stderr().flush();
+}
+
+#[cfg(unix)]
+fn make_executable(path: &str) -> Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+    let mut perms = fs::metadata(path)?.permissions();
+    perms.set_mode(perms.mode() | 0o111);
+    fs::set_permissions(path, perms)?;
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn make_executable(_path: &str) -> Result<()> {
+    Ok(())
+}
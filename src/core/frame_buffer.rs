@@ -0,0 +1,62 @@
+use crossbeam_channel::{bounded, Receiver, Sender, TrySendError};
+
+/// Bounded ring buffer of decoded RGB frames shared between the decoder
+/// reader thread (producer) and the playback loop (consumer).
+///
+/// Backed by a `crossbeam_channel` so producer and consumer never contend on
+/// a single lock; the bound gives us natural back-pressure instead of letting
+/// the reader thread run arbitrarily far ahead of the renderer.
+pub struct FrameBuffer {
+    sender: Sender<Vec<u8>>,
+    receiver: Receiver<Vec<u8>>,
+    capacity: usize,
+}
+
+/// A cheap, cloneable producer handle for pushing frames into a `FrameBuffer`.
+#[derive(Clone)]
+pub struct FrameQueue {
+    sender: Sender<Vec<u8>>,
+}
+
+impl FrameBuffer {
+    pub fn new(capacity: usize) -> Self {
+        let (sender, receiver) = bounded(capacity.max(1));
+        Self { sender, receiver, capacity: capacity.max(1) }
+    }
+
+    /// Hand out a producer handle that can be moved into the reader thread.
+    pub fn clone_queue(&self) -> FrameQueue {
+        FrameQueue { sender: self.sender.clone() }
+    }
+
+    /// Non-blocking pop; returns `None` if the buffer is currently empty.
+    pub fn pop(&self) -> Option<Vec<u8>> {
+        self.receiver.try_recv().ok()
+    }
+
+    /// Fraction of capacity currently occupied, for diagnostics.
+    pub fn fill_level(&self) -> f32 {
+        self.receiver.len() as f32 / self.capacity as f32
+    }
+
+    /// Number of frames currently queued, for turning buffer occupancy into
+    /// a cached-duration figure (`queued_frames / fps * 1000` ms).
+    pub fn queued_frames(&self) -> usize {
+        self.receiver.len()
+    }
+
+    /// Discard all buffered frames, e.g. right after a seek so stale frames
+    /// don't get rendered before the reader thread catches up to the new
+    /// position.
+    pub fn drain(&self) {
+        while self.receiver.try_recv().is_ok() {}
+    }
+}
+
+impl FrameQueue {
+    /// Non-blocking push; returns `Err` if the buffer is full so the caller
+    /// can decide whether to wait, drop the frame, or back off.
+    pub fn push(&self, frame: Vec<u8>) -> Result<(), TrySendError<Vec<u8>>> {
+        self.sender.try_send(frame)
+    }
+}
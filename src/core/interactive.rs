@@ -1,7 +1,8 @@
 use anyhow::{Result, Context};
-use dialoguer::{theme::ColorfulTheme, Select};
+use dialoguer::{theme::ColorfulTheme, Confirm, Input, Select};
 use std::path::{Path, PathBuf};
 use std::fs;
+use crate::core::audio_manager::AudioChannel;
 use crate::core::display_manager::DisplayMode;
 use crate::core::player;
 use opencv::prelude::*;
@@ -241,6 +242,38 @@ impl Drop for TerminalSettingsGuard {
     }
 }
 
+/// Number of audio channels in `path`'s first audio stream, via `ffprobe`.
+/// Returns `None` if `ffprobe` is missing or the stream can't be read, in
+/// which case callers should assume mono and skip channel selection.
+fn probe_channel_count(path: &Path) -> Option<u16> {
+    let output = std::process::Command::new("ffprobe")
+        .arg("-v").arg("error")
+        .arg("-select_streams").arg("a:0")
+        .arg("-show_entries").arg("stream=channels")
+        .arg("-of").arg("default=noprint_wrappers=1:nokey=1")
+        .arg(path)
+        .output()
+        .ok()?;
+
+    if !output.status.success() {
+        return None;
+    }
+
+    String::from_utf8_lossy(&output.stdout).trim().parse::<u16>().ok()
+}
+
+/// The ffmpeg `-af` filter that isolates `channel`, or `None` for
+/// `AudioChannel::Stereo` where the full mix should pass through untouched.
+fn pan_filter_for_channel(channel: AudioChannel) -> Option<String> {
+    match channel {
+        AudioChannel::Stereo => None,
+        AudioChannel::Left => Some("pan=mono|c0=c0".to_string()),
+        AudioChannel::Right => Some("pan=mono|c0=c1".to_string()),
+        AudioChannel::Mix => Some("pan=mono|c0=0.5*c0+0.5*c1".to_string()),
+        AudioChannel::Index(idx) => Some(format!("pan=mono|c0=c{}", idx)),
+    }
+}
+
 pub fn run_interactive_mode() -> Result<()> {
     // 1. Video Selection
     let video_dir = Path::new("assets/vidio");
@@ -302,6 +335,31 @@ pub fn run_interactive_mode() -> Result<()> {
         Some(&audios[audio_selection - 1])
     };
 
+    // 2b. Audio Channel Selection (e.g. camera recordings with a lavalier
+    // mic on one stereo channel and room ambience on the other). Only offer
+    // the per-channel options when the source actually has a stereo (or
+    // wider) track to split; a mono source has nothing to select from.
+    let channel_source = selected_audio.map(|p| p.as_path()).unwrap_or(selected_video.as_path());
+    let channel_count = probe_channel_count(channel_source).unwrap_or(1);
+
+    let audio_channel = if channel_count >= 2 {
+        let channel_options = vec!["스테레오 (원본)", "왼쪽 채널", "오른쪽 채널", "다운믹스 (평균)"];
+        let channel_selection = Select::with_theme(&ColorfulTheme::default())
+            .with_prompt("오디오 채널 선택")
+            .default(0)
+            .items(&channel_options)
+            .interact()?;
+
+        match channel_selection {
+            1 => AudioChannel::Left,
+            2 => AudioChannel::Right,
+            3 => AudioChannel::Mix,
+            _ => AudioChannel::Stereo,
+        }
+    } else {
+        AudioChannel::Stereo
+    };
+
     // 3. Render Mode
     let modes = vec!["RGB 컬러 모드 (추천)", "ASCII 텍스트 모드"];
     let mode_selection = Select::with_theme(&ColorfulTheme::default())
@@ -312,6 +370,16 @@ pub fn run_interactive_mode() -> Result<()> {
 
     let mode = if mode_selection == 0 { DisplayMode::Rgb } else { DisplayMode::Ascii };
 
+    // 3b. Dithering (reduces banding on gradients in ASCII/ANSI256 modes, at
+    // the cost of re-touching every cell instead of just the changed ones)
+    let dither_options = vec!["끄기 (기본)", "켜기 (그라데이션 번짐 방지)"];
+    let dither_selection = Select::with_theme(&ColorfulTheme::default())
+        .with_prompt("디더링 (Floyd-Steinberg)")
+        .default(0)
+        .items(&dither_options)
+        .interact()?;
+    let dither = dither_selection == 1;
+
     // 4. Aspect Ratio Mode
     let aspect_modes = vec![
         "Fit (레터박스) - 원본 비율 유지 (검은 여백)",
@@ -408,12 +476,18 @@ pub fn run_interactive_mode() -> Result<()> {
             final_audio_path = Some(extracted_path.to_string_lossy().to_string());
         } else {
             println!("ℹ️  오디오 추출 중...");
-            // Call ffmpeg
-            let status = std::process::Command::new("ffmpeg")
-                .arg("-i").arg(selected_video)
+            // Call ffmpeg, baking the selected channel into the extracted
+            // file itself (via `-af pan=...`) rather than leaving the full
+            // mix to be downmixed later on every playback.
+            let mut cmd = std::process::Command::new("ffmpeg");
+            cmd.arg("-i").arg(selected_video)
                 .arg("-vn")
                 .arg("-acodec").arg("libmp3lame")
-                .arg("-q:a").arg("2")
+                .arg("-q:a").arg("2");
+            if let Some(filter) = pan_filter_for_channel(audio_channel) {
+                cmd.arg("-af").arg(filter);
+            }
+            let status = cmd
                 .arg(&extracted_path)
                 .arg("-y")
                 .arg("-hide_banner")
@@ -433,6 +507,93 @@ pub fn run_interactive_mode() -> Result<()> {
         }
     }
 
+    // 5a. Optional trim: start/end offsets (seconds or MM:SS), skippable by
+    // leaving the prompt blank.
+    let start_input: String = Input::with_theme(&ColorfulTheme::default())
+        .with_prompt("시작 시점 (초 또는 MM:SS, 비워두면 처음부터)")
+        .allow_empty(true)
+        .default(String::new())
+        .interact_text()?;
+    let start_time: Option<f64> = if start_input.trim().is_empty() {
+        None
+    } else {
+        Some(start_input.trim().parse::<player::TimeSpec>().map_err(anyhow::Error::msg)?.0)
+    };
+
+    let end_input: String = Input::with_theme(&ColorfulTheme::default())
+        .with_prompt("종료 시점 (초 또는 MM:SS, 비워두면 끝까지)")
+        .allow_empty(true)
+        .default(String::new())
+        .interact_text()?;
+    let end_time: Option<f64> = if end_input.trim().is_empty() {
+        None
+    } else {
+        Some(end_input.trim().parse::<player::TimeSpec>().map_err(anyhow::Error::msg)?.0)
+    };
+
+    // Fast-forward ranges, one per prompt, entered as "START,END[,FACTOR]";
+    // leave blank to stop adding more.
+    let mut fast_ranges: Vec<player::FastRange> = Vec::new();
+    loop {
+        let fast_input: String = Input::with_theme(&ColorfulTheme::default())
+            .with_prompt("빨리 감기 구간 추가 (START,END[,FACTOR], 비워두면 완료)")
+            .allow_empty(true)
+            .default(String::new())
+            .interact_text()?;
+        if fast_input.trim().is_empty() {
+            break;
+        }
+        match fast_input.trim().parse::<player::FastRange>() {
+            Ok(range) => fast_ranges.push(range),
+            Err(e) => println!("⚠️  빨리 감기 구간 무시됨: {}", e),
+        }
+    }
+
+    // 5b. Offer to save this session's settings as a replayable TOML
+    // project, so it can be re-run headlessly via `Commands::Project`
+    // without walking through these prompts again.
+    let save_confirm = Confirm::with_theme(&ColorfulTheme::default())
+        .with_prompt("이 설정을 TOML 프로젝트 파일로 저장할까요? (헤드리스 재생/CI용)")
+        .default(false)
+        .interact()?;
+
+    if save_confirm {
+        let default_name = format!("{}.toml", selected_video.file_stem().unwrap().to_string_lossy());
+        let out_path: String = Input::with_theme(&ColorfulTheme::default())
+            .with_prompt("저장할 파일 경로")
+            .default(default_name)
+            .interact_text()?;
+
+        let project = crate::core::project::ProjectFile {
+            source: crate::core::project::SourceSection {
+                files: vec![selected_video.to_string_lossy().to_string()],
+            },
+            render: crate::core::project::RenderSection {
+                mode: crate::core::project::display_mode_name(mode).to_string(),
+                aspect: match aspect_selection {
+                    0 => "fit",
+                    1 => "fill",
+                    _ => "stretch",
+                }
+                .to_string(),
+                width: Some(target_w),
+                height: Some(target_h),
+                fullscreen: false,
+                dither,
+                start: if start_input.trim().is_empty() { None } else { Some(start_input.trim().to_string()) },
+                end: if end_input.trim().is_empty() { None } else { Some(end_input.trim().to_string()) },
+            },
+            audio: final_audio_path.clone().map(|path| crate::core::project::AudioSection { path }),
+            font: None,
+            questions: None,
+        };
+
+        match project.save(Path::new(&out_path)) {
+            Ok(()) => println!("✅ 프로젝트 저장됨: {}", out_path),
+            Err(e) => println!("⚠️  프로젝트 저장 실패: {}", e),
+        }
+    }
+
     let video_path_str = selected_video.to_string_lossy();
     player::play_realtime(
         &video_path_str,
@@ -441,8 +602,30 @@ pub fn run_interactive_mode() -> Result<()> {
         target_h,
         0, // 0 means native fps
         mode,
-        fill
+        None, // Recording isn't wired up in interactive mode yet
+        audio_channel,
+        if dither {
+            crate::core::display_manager::DitherMode::ErrorDiffusion
+        } else {
+            crate::core::display_manager::DitherMode::None
+        },
+        true, // Serpentine scanning is always on when dithering is enabled interactively
+        start_time,
+        end_time,
+        fast_ranges,
+        crate::core::overlay::Overlay::default(),
+        false, // mute
+        0.0,   // av_offset
+        None,  // record_cast
+        crate::core::video_decoder::DecodeBackend::Auto,
+        None,  // record_cells
+        false, // use_stdin
+        crate::core::render_target::RenderTarget::HalfBlock,
     )?;
 
+    // Fill mode cropping isn't implemented in the decoder yet; `fill` is
+    // collected above for when that lands.
+    let _ = fill;
+
     Ok(())
 }
@@ -1,9 +1,19 @@
+pub mod asciicast;
 pub mod audio_manager;
 pub mod display_manager;
 pub mod extractor;
 
 pub mod frame_buffer;
 pub mod frame_manager;
+pub mod interactive;
+pub mod overlay;
+pub mod palette;
 pub mod player;
 pub mod processor;
+pub mod project;
+pub mod recorder;
+pub mod render_target;
+pub mod replay;
 pub mod video_decoder;
+pub mod video_source;
+pub mod y4m_decoder;
@@ -0,0 +1,195 @@
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::Path;
+
+use crate::core::processor::CellData;
+
+/// One timed caption, mirroring the external lecture tool's
+/// `questions = [[start, end, text]]` project entries.
+#[derive(Debug, Clone)]
+pub struct Caption {
+    pub start: f64,
+    pub end: f64,
+    pub text: String,
+}
+
+/// A sorted list of timed captions, queried once per frame by playback time
+/// and composited onto the bottom rows of the rendered cell grid before
+/// `DisplayManager::render_diff`, so the caption participates in diffing
+/// like any other cell content and disappears on its own once its time
+/// window passes.
+#[derive(Debug, Clone, Default)]
+pub struct Overlay {
+    captions: Vec<Caption>,
+}
+
+const MAX_LINES: usize = 3;
+const OVERLAY_FG: (u8, u8, u8) = (255, 255, 255);
+const OVERLAY_BG: (u8, u8, u8) = (20, 20, 20);
+
+impl Overlay {
+    pub fn new(mut captions: Vec<Caption>) -> Self {
+        captions.sort_by(|a, b| a.start.partial_cmp(&b.start).unwrap());
+        Self { captions }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.captions.is_empty()
+    }
+
+    /// Load captions from a sidecar file: `.srt` (standard SubRip) or
+    /// `.json` (`[[start_secs, end_secs, text], ...]`, the same shape as the
+    /// project TOML's `questions` array).
+    pub fn load(path: &Path) -> Result<Self> {
+        match path.extension().and_then(|e| e.to_str()).map(|e| e.to_ascii_lowercase()).as_deref() {
+            Some("srt") => Self::load_srt(path),
+            Some("json") => Self::load_json(path),
+            _ => anyhow::bail!("Unsupported overlay file '{}' (expected .srt or .json)", path.display()),
+        }
+    }
+
+    fn load_json(path: &Path) -> Result<Self> {
+        let text = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read overlay file {}", path.display()))?;
+        let raw: Vec<(f64, f64, String)> = serde_json::from_str(&text)
+            .with_context(|| format!("Failed to parse overlay JSON {}", path.display()))?;
+        Ok(Self::new(raw.into_iter().map(|(start, end, text)| Caption { start, end, text }).collect()))
+    }
+
+    fn load_srt(path: &Path) -> Result<Self> {
+        let text = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read overlay file {}", path.display()))?;
+        let mut captions = Vec::new();
+        let mut lines = text.lines().peekable();
+
+        while lines.peek().is_some() {
+            while matches!(lines.peek(), Some(l) if l.trim().is_empty()) {
+                lines.next();
+            }
+            if lines.peek().is_none() {
+                break;
+            }
+            lines.next(); // numeric cue index, unused
+
+            let Some(timing) = lines.next() else { break };
+            let Some((start, end)) = timing.split_once("-->") else { continue };
+            let (Ok(start), Ok(end)) = (parse_srt_timestamp(start.trim()), parse_srt_timestamp(end.trim())) else {
+                continue;
+            };
+
+            let mut text_lines = Vec::new();
+            for line in lines.by_ref() {
+                if line.trim().is_empty() {
+                    break;
+                }
+                text_lines.push(line.trim().to_string());
+            }
+
+            captions.push(Caption { start, end, text: text_lines.join(" ") });
+        }
+
+        Ok(Self::new(captions))
+    }
+
+    /// The caption active at playback time `t`, if any.
+    pub fn active_at(&self, t: f64) -> Option<&str> {
+        self.captions.iter().find(|c| t >= c.start && t <= c.end).map(|c| c.text.as_str())
+    }
+
+    /// If a caption is active at `t`, merge it onto the bottom rows of
+    /// `cells` in place. Callers apply this after `FrameProcessor` builds
+    /// the cell grid and before handing it to `render_diff`.
+    pub fn apply(&self, cells: &mut [CellData], width: usize, t: f64) {
+        if let Some(text) = self.active_at(t) {
+            composite_caption(cells, width, text);
+        }
+    }
+}
+
+fn parse_srt_timestamp(s: &str) -> Result<f64> {
+    let (hms, millis) = s.split_once(',').context("malformed SRT timestamp")?;
+    let mut parts = hms.split(':');
+    let h: f64 = parts.next().context("malformed SRT timestamp")?.parse()?;
+    let m: f64 = parts.next().context("malformed SRT timestamp")?.parse()?;
+    let secs: f64 = parts.next().context("malformed SRT timestamp")?.parse()?;
+    let ms: f64 = millis.parse()?;
+    Ok(h * 3600.0 + m * 60.0 + secs + ms / 1000.0)
+}
+
+/// Greedily wrap `text` at whitespace so no line exceeds `max_width`
+/// columns. A single word longer than `max_width` is hard-cut as a last
+/// resort so it can't spill past the frame.
+fn wrap_text(text: &str, max_width: usize) -> Vec<String> {
+    if max_width == 0 {
+        return vec![text.to_string()];
+    }
+
+    let mut lines = Vec::new();
+    let mut current = String::new();
+
+    for word in text.split_whitespace() {
+        let mut word = word;
+        loop {
+            let sep_len = if current.is_empty() { 0 } else { 1 };
+            if current.chars().count() + sep_len + word.chars().count() <= max_width {
+                if sep_len == 1 {
+                    current.push(' ');
+                }
+                current.push_str(word);
+                break;
+            }
+            if !current.is_empty() {
+                lines.push(std::mem::take(&mut current));
+                continue;
+            }
+            let cut = word.char_indices().nth(max_width).map(|(i, _)| i).unwrap_or(word.len());
+            let (head, tail) = word.split_at(cut);
+            lines.push(head.to_string());
+            word = tail;
+            if word.is_empty() {
+                break;
+            }
+        }
+    }
+    if !current.is_empty() {
+        lines.push(current);
+    }
+    lines
+}
+
+/// Word-wrap and center-align `text`, then stamp it onto the bottom rows of
+/// `cells` with a solid high-contrast background so it stays legible over
+/// any frame content, in any `DisplayMode`.
+fn composite_caption(cells: &mut [CellData], width: usize, text: &str) {
+    if width == 0 {
+        return;
+    }
+    let height = cells.len() / width;
+    if height == 0 {
+        return;
+    }
+
+    let mut lines = wrap_text(text, width.saturating_sub(2));
+    if lines.len() > MAX_LINES {
+        lines.drain(0..lines.len() - MAX_LINES);
+    }
+
+    let start_row = height.saturating_sub(lines.len());
+    for (i, line) in lines.iter().enumerate() {
+        let row = start_row + i;
+        let pad = width.saturating_sub(line.chars().count()) / 2;
+        let row_cells = &mut cells[row * width..(row + 1) * width];
+
+        for cell in row_cells.iter_mut() {
+            cell.char = ' ';
+            cell.fg = OVERLAY_FG;
+            cell.bg = OVERLAY_BG;
+        }
+        for (col_offset, ch) in line.chars().enumerate() {
+            let col = pad + col_offset;
+            if col < width {
+                row_cells[col].char = ch;
+            }
+        }
+    }
+}
@@ -0,0 +1,249 @@
+//! Per-frame 256-color palette generation via median cut.
+//!
+//! `DisplayMode::Ansi256` used to quantize every color against the
+//! terminal's fixed 6x6x6 color cube (`renderer::quantizer::ColorQuantizer`),
+//! which loses a lot of mid-tone detail. Building a palette tailored to the
+//! frame actually being shown, and pushing it to the terminal via `OSC 4`
+//! palette-redefinition escapes, gets much closer to truecolor on terminals
+//! that only support 256 indexed colors.
+
+/// 32 levels per channel (5 bits) keeps the histogram to 32^3 = 32768
+/// buckets, the same "bucket first for speed" tradeoff `ColorQuantizer`
+/// makes for its LUT: cheap enough to rebuild every frame, fine enough that
+/// two colors in the same bucket are visually indistinguishable anyway.
+const BUCKET_BITS: u32 = 5;
+const BUCKET_DIM: usize = 1 << BUCKET_BITS; // 32
+const BUCKET_SHIFT: u32 = 8 - BUCKET_BITS; // 3
+const BUCKET_COUNT: usize = BUCKET_DIM * BUCKET_DIM * BUCKET_DIM;
+
+const MAX_COLORS: usize = 256;
+
+#[derive(Clone, Copy, Default)]
+struct Bucket {
+    r_sum: u64,
+    g_sum: u64,
+    b_sum: u64,
+    count: u64,
+}
+
+impl Bucket {
+    fn mean(&self) -> (u8, u8, u8) {
+        if self.count == 0 {
+            return (0, 0, 0);
+        }
+        ((self.r_sum / self.count) as u8, (self.g_sum / self.count) as u8, (self.b_sum / self.count) as u8)
+    }
+}
+
+fn bucket_index(r: u8, g: u8, b: u8) -> usize {
+    let r = (r >> BUCKET_SHIFT) as usize;
+    let g = (g >> BUCKET_SHIFT) as usize;
+    let b = (b >> BUCKET_SHIFT) as usize;
+    (r * BUCKET_DIM + g) * BUCKET_DIM + b
+}
+
+/// A box of buckets being split by the median-cut algorithm.
+struct ColorBox {
+    buckets: Vec<usize>,
+    count: u64,
+}
+
+/// A 256-entry (or fewer, for low-color frames) palette built from a
+/// frame's actual colors, plus a precomputed bucket-to-index lookup so
+/// quantizing a pixel at render time is a single array read rather than a
+/// nearest-color search.
+pub struct Palette {
+    colors: Vec<(u8, u8, u8)>,
+    bucket_to_index: Vec<u8>,
+}
+
+impl Palette {
+    /// Build a palette from `pixels` (RGB triples drawn from whatever the
+    /// caller considers "the frame" - raw decoded pixels or, as
+    /// `DisplayManager` does, the fg/bg color of every rendered cell).
+    pub fn build<I: IntoIterator<Item = (u8, u8, u8)>>(pixels: I) -> Self {
+        // 1. Collect all pixel RGB triples into a 32^3 histogram.
+        let mut buckets = vec![Bucket::default(); BUCKET_COUNT];
+        for (r, g, b) in pixels {
+            let bucket = &mut buckets[bucket_index(r, g, b)];
+            bucket.r_sum += r as u64;
+            bucket.g_sum += g as u64;
+            bucket.b_sum += b as u64;
+            bucket.count += 1;
+        }
+
+        let occupied: Vec<usize> = (0..BUCKET_COUNT).filter(|&i| buckets[i].count > 0).collect();
+        if occupied.is_empty() {
+            return Self { colors: vec![(0, 0, 0)], bucket_to_index: vec![0; BUCKET_COUNT] };
+        }
+
+        let total_count = occupied.iter().map(|&i| buckets[i].count).sum();
+        let mut boxes = vec![ColorBox { buckets: occupied, count: total_count }];
+
+        // 2. Repeatedly split the most-populated splittable box along its
+        // widest channel, at the pixel-count median, until there are 256
+        // boxes or nothing left worth splitting.
+        while boxes.len() < MAX_COLORS {
+            let Some((split_at, _)) =
+                boxes.iter().enumerate().filter(|(_, b)| b.buckets.len() > 1).max_by_key(|(_, b)| b.count)
+            else {
+                break;
+            };
+
+            let target = boxes.swap_remove(split_at);
+            let channel = widest_channel(&buckets, &target.buckets);
+
+            let mut sorted = target.buckets;
+            sorted.sort_by_key(|&i| channel_mean(&buckets[i], channel));
+
+            let half = target.count / 2;
+            let mut running = 0u64;
+            let mut split_idx = sorted.len() - 1;
+            for (pos, &i) in sorted.iter().enumerate() {
+                running += buckets[i].count;
+                if running >= half {
+                    split_idx = pos;
+                    break;
+                }
+            }
+            // Keep both halves non-empty even if the median lands on the
+            // very first bucket.
+            let split_idx = split_idx.clamp(1, sorted.len() - 1);
+
+            let (left, right) = sorted.split_at(split_idx);
+            let left_count = left.iter().map(|&i| buckets[i].count).sum();
+            let right_count = right.iter().map(|&i| buckets[i].count).sum();
+            boxes.push(ColorBox { buckets: left.to_vec(), count: left_count });
+            boxes.push(ColorBox { buckets: right.to_vec(), count: right_count });
+        }
+
+        // 3. Each box's representative color is the count-weighted mean of
+        // its buckets, and every bucket in the box maps to that palette
+        // entry.
+        let mut colors = Vec::with_capacity(boxes.len());
+        let mut bucket_to_index = vec![0u8; BUCKET_COUNT];
+        for (palette_idx, b) in boxes.iter().enumerate() {
+            let (mut r, mut g, mut b_sum, mut count) = (0u64, 0u64, 0u64, 0u64);
+            for &i in &b.buckets {
+                let bucket = &buckets[i];
+                r += bucket.r_sum;
+                g += bucket.g_sum;
+                b_sum += bucket.b_sum;
+                count += bucket.count;
+                bucket_to_index[i] = palette_idx as u8;
+            }
+            colors.push(((r / count) as u8, (g / count) as u8, (b_sum / count) as u8));
+        }
+
+        Self { colors, bucket_to_index }
+    }
+
+    /// The index of the palette entry whose median-cut box holds the
+    /// histogram bucket of `(r, g, b)`, via the lookup built in `build`.
+    /// A color whose bucket received no pixels in `build` maps to index 0;
+    /// no nearest-color search is done for it.
+    pub fn quantize(&self, r: u8, g: u8, b: u8) -> u8 {
+        self.bucket_to_index[bucket_index(r, g, b)]
+    }
+
+    /// The palette's actual color for `index`, used to compute dithering
+    /// error against the original color.
+    pub fn color_at(&self, index: u8) -> (u8, u8, u8) {
+        self.colors.get(index as usize).copied().unwrap_or((0, 0, 0))
+    }
+
+    /// Number of colors actually in this palette (<= 256). Never empty:
+    /// `build` always produces at least one entry, even from no pixels.
+    pub fn len(&self) -> usize {
+        self.colors.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.colors.is_empty()
+    }
+
+    /// `OSC 4` palette-redefinition escapes for every entry in this
+    /// palette, meant to be written once at the start of a frame so the
+    /// `\x1b[38;5;{i}m` / `\x1b[48;5;{i}m` sequences that follow resolve to
+    /// this frame's colors instead of the terminal's default cube.
+    pub fn osc4_sequence(&self) -> String {
+        let mut out = String::with_capacity(self.colors.len() * 24);
+        for (i, &(r, g, b)) in self.colors.iter().enumerate() {
+            out.push_str(&format!("\x1b]4;{};rgb:{:02x}/{:02x}/{:02x}\x1b\\", i, r, g, b));
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_input_yields_single_black_entry() {
+        let palette = Palette::build(std::iter::empty());
+        assert_eq!(palette.color_at(0), (0, 0, 0));
+        assert_eq!(palette.quantize(200, 10, 10), 0);
+    }
+
+    #[test]
+    fn single_color_input_yields_that_color() {
+        let palette = Palette::build(std::iter::repeat((12, 34, 56)).take(100));
+        assert_eq!(palette.color_at(0), (12, 34, 56));
+        assert_eq!(palette.quantize(12, 34, 56), 0);
+    }
+
+    #[test]
+    fn distinct_colors_get_distinct_palette_entries() {
+        let pixels = vec![(255, 0, 0), (0, 255, 0), (0, 0, 255)];
+        let palette = Palette::build(pixels);
+        let red = palette.quantize(255, 0, 0);
+        let green = palette.quantize(0, 255, 0);
+        let blue = palette.quantize(0, 0, 255);
+        assert_ne!(red, green);
+        assert_ne!(green, blue);
+        assert_ne!(red, blue);
+    }
+
+    #[test]
+    fn never_produces_more_than_max_colors() {
+        let pixels = (0u32..20000).map(|i| ((i % 256) as u8, ((i / 3) % 256) as u8, ((i / 7) % 256) as u8));
+        let palette = Palette::build(pixels);
+        assert!(palette.colors.len() <= MAX_COLORS);
+    }
+
+    #[test]
+    fn quantized_color_is_close_to_original_for_a_tight_cluster() {
+        let pixels = vec![(100, 100, 100), (102, 101, 99), (98, 99, 101)];
+        let palette = Palette::build(pixels);
+        let idx = palette.quantize(100, 100, 100);
+        let (r, g, b) = palette.color_at(idx);
+        assert!((r as i32 - 100).abs() <= 4);
+        assert!((g as i32 - 100).abs() <= 4);
+        assert!((b as i32 - 100).abs() <= 4);
+    }
+}
+
+fn channel_mean(bucket: &Bucket, channel: u8) -> u8 {
+    let (r, g, b) = bucket.mean();
+    match channel {
+        0 => r,
+        1 => g,
+        _ => b,
+    }
+}
+
+/// The channel (0=R, 1=G, 2=B) with the largest min-max spread of bucket
+/// mean colors across `indices`, the axis median cut splits along.
+fn widest_channel(buckets: &[Bucket], indices: &[usize]) -> u8 {
+    let mut lo = [255u8; 3];
+    let mut hi = [0u8; 3];
+    for &i in indices {
+        let (r, g, b) = buckets[i].mean();
+        for (c, v) in [r, g, b].into_iter().enumerate() {
+            lo[c] = lo[c].min(v);
+            hi[c] = hi[c].max(v);
+        }
+    }
+    (0..3u8).max_by_key(|&c| hi[c as usize] as i32 - lo[c as usize] as i32).unwrap()
+}
@@ -1,14 +1,158 @@
 use anyhow::{Context, Result};
 
-use std::io::Read;
 use std::sync::atomic::{AtomicBool, Ordering};
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
 use std::thread;
 
 use crate::core::display_manager::{DisplayManager, DisplayMode};
-use crate::core::audio_manager::AudioManager;
+use crate::core::audio_manager::{AudioChannel, AudioManager};
+use crate::core::overlay::Overlay;
+use crate::core::recorder::Recorder;
+use crate::core::video_source::VideoSource;
+use crate::core::render_target::RenderTarget;
 
+/// Parses `--start`/`--end` and the boundaries inside a `FastRange`: either
+/// plain seconds (`"90"`, `"90.5"`) or `MM:SS` (`"01:30"`).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TimeSpec(pub f64);
+
+impl std::str::FromStr for TimeSpec {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let s = s.trim();
+        match s.split_once(':') {
+            Some((mins, secs)) => {
+                let mins: f64 = mins.parse().map_err(|_| format!("invalid minutes in time '{}'", s))?;
+                let secs: f64 = secs.parse().map_err(|_| format!("invalid seconds in time '{}'", s))?;
+                Ok(TimeSpec(mins * 60.0 + secs))
+            }
+            None => s
+                .parse::<f64>()
+                .map(TimeSpec)
+                .map_err(|_| format!("invalid time '{}' (expected seconds or MM:SS)", s)),
+        }
+    }
+}
+
+/// A `--fast START,END[,FACTOR]` speed-up window: frames whose timestamp
+/// falls in `[start, end]` are rendered at `factor`x speed by dropping
+/// `factor - 1` out of every `factor` frames. `START`/`END` accept the same
+/// seconds-or-`MM:SS` syntax as `TimeSpec`; `FACTOR` defaults to `2.0`.
+/// A single comma-joined token (rather than clap's multi-value `num_args`)
+/// keeps each repeated `--fast` occurrence unambiguous, the same way
+/// `AudioChannel` is parsed from one string instead of several flags.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FastRange {
+    pub start: f64,
+    pub end: f64,
+    pub factor: f64,
+}
+
+impl std::str::FromStr for FastRange {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let parts: Vec<&str> = s.split(',').map(str::trim).collect();
+        if parts.len() < 2 || parts.len() > 3 {
+            return Err(format!("invalid --fast range '{}' (expected START,END[,FACTOR])", s));
+        }
+
+        let start = parts[0].parse::<TimeSpec>()?.0;
+        let end = parts[1].parse::<TimeSpec>()?.0;
+        let factor = match parts.get(2) {
+            Some(f) => f.parse::<f64>().map_err(|_| format!("invalid factor in --fast range '{}'", s))?,
+            None => 2.0,
+        };
+
+        if end <= start {
+            return Err(format!("--fast range '{}' has end <= start", s));
+        }
+        if factor <= 1.0 {
+            return Err(format!("--fast range '{}' has factor <= 1.0 (must speed up, not slow down)", s));
+        }
+
+        Ok(FastRange { start, end, factor })
+    }
+}
+
+/// What a single keypress means for a playing/paused video, independent of
+/// how each player loop actually applies it. Kept as a flat enum (rather
+/// than a decode-style Normal/Waiting/Flush state machine) since transport
+/// controls are just discrete commands, not a pipeline with its own state.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PlayerControl {
+    Quit,
+    TogglePause,
+    /// Relative seek in seconds (negative = backward).
+    Seek(f64),
+    /// Step one frame forward (`1`) or backward (`-1`); only meaningful
+    /// while paused.
+    StepFrame(i64),
+    SpeedDown,
+    SpeedUp,
+    ToggleOsd,
+    Screenshot,
+}
+
+/// Poll for a single keypress (non-blocking) and translate it into a
+/// `PlayerControl`. Shared by `play_realtime` and `main::play_animation` so
+/// both players respond to the same transport-control layout: space =
+/// pause, left/right = seek +/-5s, `,`/`.` = step one frame while paused,
+/// `[`/`]` = speed, `o` = toggle OSD, `s` = screenshot, `q` = quit.
+pub fn poll_transport_control() -> Result<Option<PlayerControl>> {
+    use crossterm::event::{Event, KeyCode};
+
+    if !crossterm::event::poll(Duration::from_millis(0))? {
+        return Ok(None);
+    }
+    let Event::Key(key) = crossterm::event::read()? else {
+        return Ok(None);
+    };
+
+    Ok(match key.code {
+        KeyCode::Char('q') => Some(PlayerControl::Quit),
+        KeyCode::Char(' ') => Some(PlayerControl::TogglePause),
+        KeyCode::Left => Some(PlayerControl::Seek(-5.0)),
+        KeyCode::Right => Some(PlayerControl::Seek(5.0)),
+        KeyCode::Char(',') => Some(PlayerControl::StepFrame(-1)),
+        KeyCode::Char('.') => Some(PlayerControl::StepFrame(1)),
+        KeyCode::Char('[') => Some(PlayerControl::SpeedDown),
+        KeyCode::Char(']') => Some(PlayerControl::SpeedUp),
+        KeyCode::Char('o') => Some(PlayerControl::ToggleOsd),
+        KeyCode::Char('s') => Some(PlayerControl::Screenshot),
+        _ => None,
+    })
+}
+
+/// Write `rgb` (tightly packed `width * height * 3` RGB bytes) out as a
+/// timestamped PNG via OpenCV's `imwrite`, mpv-screenshot style.
+pub(crate) fn write_screenshot(rgb: &[u8], width: u32, height: u32) -> Result<()> {
+    use opencv::prelude::*;
+
+    let mut bgr = rgb.to_vec();
+    for pixel in bgr.chunks_exact_mut(3) {
+        pixel.swap(0, 2);
+    }
+
+    let mut mat = opencv::core::Mat::zeros(height as i32, width as i32, opencv::core::CV_8UC3)?.to_mat()?;
+    mat.data_bytes_mut()?.copy_from_slice(&bgr);
+
+    let millis = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis();
+    let filename = format!("screenshot_{}.png", millis);
+
+    opencv::imgcodecs::imwrite(&filename, &mat, &opencv::core::Vector::new())
+        .with_context(|| format!("Failed to write screenshot {}", filename))?;
+    println!("📸 Saved {}", filename);
+
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
 pub fn play_realtime(
     video_path: &str,
     audio_path: Option<&str>,
@@ -16,22 +160,57 @@ pub fn play_realtime(
     height: u32,
     fps: u32,
     mode: DisplayMode,
+    record_path: Option<&str>,
+    audio_channel: AudioChannel,
+    dither: crate::core::display_manager::DitherMode,
+    serpentine: bool,
+    start: Option<f64>,
+    end: Option<f64>,
+    mut fast_ranges: Vec<FastRange>,
+    overlay: Overlay,
+    mute: bool,
+    av_offset: f64,
+    record_cast: Option<&str>,
+    backend: crate::core::video_decoder::DecodeBackend,
+    record_cells: Option<&str>,
+    use_stdin: bool,
+    render_target: RenderTarget,
 ) -> Result<()> {
     // 1. Initialize Display & Audio
-    let mut display = DisplayManager::new(mode)?;
+    // Resolved once, up front, so every later match on `mode`/`render_target`
+    // in this function agrees with what actually gets rendered.
+    let mode = crate::core::display_manager::resolve_display_mode(mode);
+    let render_target = render_target.resolve();
+    let mut display = DisplayManager::new(mode)?.with_dither(dither).with_serpentine(serpentine)
+        .with_record_cast(record_cast)?.with_record_cells(record_cells);
     let audio = AudioManager::new()?;
 
     // 2. Start Audio
-    if let Some(path) = audio_path {
-        audio.play(path)?;
+    if !mute {
+        if let Some(path) = audio_path {
+            audio.play(path, audio_channel)?;
+        }
     }
 
-    // 3. Start Video Decoder
+    // Checked with a single advancing index per frame (below), so lookups
+    // stay O(1) regardless of how many ranges are configured.
+    fast_ranges.sort_by(|a, b| a.start.partial_cmp(&b.start).unwrap());
+
+    // 3. Start Video Decoder: OpenCV against a file, or a raw YUV4MPEG2
+    // stream on stdin (e.g. `ffmpeg -i in.mkv -f yuv4mpegpipe - | gascii
+    // play-live --stdin -v -`) when no OpenCV build is available.
     println!("Initializing video decoder...");
-    let mut decoder = crate::core::video_decoder::VideoDecoder::new(video_path, width, height, fps)?;
-    let mut stdout = decoder.child.take_stdout().context("Failed to take stdout")?;
+    let source: Box<dyn VideoSource + Send> = if use_stdin {
+        Box::new(crate::core::y4m_decoder::Y4mDecoder::from_stdin(width, height)?)
+    } else {
+        Box::new(crate::core::video_decoder::VideoDecoder::new(video_path, width, height, backend)?)
+    };
+    let source = Arc::new(Mutex::new(source));
+    if let Some(start) = start {
+        source.lock().unwrap().seek(start)?;
+    }
     println!("Video decoder started. Check debug.log for details.");
-    
+
     // 4. Initialize Frame Processor (Rayon)
     let processor = crate::core::processor::FrameProcessor::new(width as usize, height as usize);
 
@@ -40,48 +219,46 @@ pub fn play_realtime(
     let frame_buffer = crate::core::frame_buffer::FrameBuffer::new(buffer_capacity);
     let queue = frame_buffer.clone_queue();
 
-    let frame_size = (width * height * 3) as usize;
-
-    // 6. Spawn FFmpeg Reader Thread (Producer)
+    // 6. Spawn Decoder Reader Thread (Producer)
     let running_reader = Arc::new(AtomicBool::new(true));
     let r_clone = running_reader.clone();
-    
+    let source_reader = source.clone();
+
     let reader_handle = thread::spawn(move || {
-        let mut buffer = vec![0u8; frame_size];
         let mut frames_read = 0u64;
-        
+
         while r_clone.load(Ordering::SeqCst) {
-            match stdout.read_exact(&mut buffer) {
-                Ok(_) => {
-                    // BLOCKING push: Wait until buffer has space
-                    // This ensures we never drop frames and maintain perfect sync
-                    while queue.push(buffer.clone()).is_err() {
-                        // Buffer full - wait for consumer to catch up
-                        thread::sleep(Duration::from_micros(100));
-                        
-                        // Check if we should exit
-                        if !r_clone.load(Ordering::SeqCst) {
-                            return;
-                        }
-                    }
-                    frames_read += 1;
-                }
-                Err(_) => {
-                    // EOF or error - exit reader thread
-                    break;
+            let frame = match source_reader.lock().unwrap().read_frame() {
+                Ok(Some(frame)) => frame,
+                Ok(None) => break, // EOF
+                Err(_) => break,   // decode error - exit reader thread
+            };
+
+            // BLOCKING push: Wait until buffer has space
+            // This ensures we never drop frames and maintain perfect sync
+            while queue.push(frame.clone()).is_err() {
+                // Buffer full - wait for consumer to catch up
+                thread::sleep(Duration::from_micros(100));
+
+                // Check if we should exit
+                if !r_clone.load(Ordering::SeqCst) {
+                    return;
                 }
             }
+            frames_read += 1;
         }
-        
-        println!("FFmpeg reader thread exited. Frames read: {}", frames_read);
+
+        println!("Decoder reader thread exited. Frames read: {}", frames_read);
     });
 
     // 7. Main Playback Loop (Consumer)
     // Wait briefly for FPS detection
     thread::sleep(Duration::from_millis(200));
-    
-    // Get actual video FPS (auto-detected from FFmpeg)
-    let actual_fps = decoder.fps_detector.get_fps_or(fps);
+
+    // Get actual video FPS, as reported by whichever source we opened,
+    // falling back to the requested rate if the source couldn't detect one.
+    let source_fps = source.lock().unwrap().get_fps();
+    let actual_fps = if source_fps > 0.0 { source_fps as f32 } else { fps as f32 };
     
     // Warn if FPS mismatch
     if (actual_fps - fps as f32).abs() > 0.5 {
@@ -91,9 +268,47 @@ pub fn play_realtime(
         println!("   Using actual video FPS for sync");
     }
     
-    let frame_duration = Duration::from_secs_f64(1.0 / actual_fps as f64);
-    let start_time = Instant::now();
-    let mut frame_idx = 0u64;
+    let mut frame_duration = Duration::from_secs_f64(1.0 / actual_fps as f64);
+
+    // Cached-duration buffering, mirroring the high-/low-water-mark model
+    // streaming players use: the ring buffer's capacity expressed as
+    // milliseconds of playback rather than a raw frame count.
+    let hwm_ms = buffer_capacity.max(1) as f64 / actual_fps as f64 * 1000.0;
+    // Refill to this fraction of capacity before leaving "Waiting", so a
+    // brief stall doesn't flap in and out of the wait state every tick.
+    let lwm_ms = hwm_ms * 0.2;
+
+    let mut frame_idx = (start.unwrap_or(0.0).max(0.0) * actual_fps as f64).round() as u64;
+    let mut start_time = Instant::now() - Duration::from_secs_f64(frame_idx as f64 / actual_fps as f64);
+    if let Some(start) = start {
+        audio.seek(start);
+    }
+
+    // Transport control state
+    let mut paused = false;
+    let mut speed: f64 = 1.0;
+    let mut show_osd = true;
+    // True while the buffer has underrun and the A/V clock is frozen
+    // waiting for the reader thread to refill it, distinct from a
+    // user-requested `paused` so the two don't clobber each other's
+    // audio pause/resume calls.
+    let mut waiting = false;
+    let mut last_measured_fps = actual_fps as f64;
+    // Advances monotonically through `fast_ranges` (sorted by start) so the
+    // per-frame lookup below never rescans earlier ranges.
+    let mut fast_idx = 0usize;
+    let mut fast_skip_count = 0u32;
+    // Last frame actually rendered, kept around so `s` can screenshot it
+    // without needing to re-pop (and thus drop) a buffer from the queue.
+    let mut last_frame: Option<Vec<u8>> = None;
+
+    // 7b. Start recorder, if requested, now that we know the real FPS
+    let recorder = match record_path {
+        // `Recorder::start` takes cell-grid rows; `height` is the pixel
+        // canvas height, two pixel rows per half-block cell.
+        Some(path) => Some(Recorder::start(path, width as usize, (height / 2) as usize, actual_fps, audio_path)?),
+        None => None,
+    };
 
     let running = Arc::new(AtomicBool::new(true));
     let r = running.clone();
@@ -105,71 +320,302 @@ pub fn play_realtime(
     // Performance metrics
     let mut last_fps_report = Instant::now();
     let mut frames_since_report = 0;
-    
+
     // Precision timing tracking
     let mut _last_frame_time = Instant::now();
     let mut cumulative_drift = Duration::ZERO;
     let mut max_drift = Duration::ZERO;
     let mut total_sleep_time = Duration::ZERO;
 
+    // Adaptive frame-pacing: a running average of how long rendering a
+    // frame actually takes, compared against the frame budget. When it
+    // can't keep up, `skip_factor` thins rendering out in whole-frame steps
+    // (render 1-in-N, decode-and-discard the rest) instead of letting
+    // playback fall further and further behind real time.
+    let mut render_cost_ema = frame_duration.as_secs_f64();
+    let mut skip_factor: u64 = 1;
+    const MAX_SKIP_FACTOR: u64 = 4;
+    let mut last_skip_adjust = Instant::now();
+
+    // ========== A/V SYNC CONFIGURATION ==========
+    // Audio-master clock: video paces itself off AudioManager::get_clock()
+    // instead of wall-clock scheduling, like ffplay's avsync. Only used when
+    // an audio track is actually playing; otherwise we fall back to the
+    // existing wall-clock schedule.
+    let audio_master = audio_path.is_some() && !mute;
+    let sync_threshold = Duration::from_millis(40);
+    // A gap larger than this (e.g. after a stall) is resynced immediately
+    // instead of dropping frames one at a time to catch up.
+    let hard_resync_threshold = Duration::from_secs(1);
+
     while running.load(Ordering::SeqCst) {
-        // Input polling
-        if crossterm::event::poll(Duration::from_millis(0))? {
-             if let crossterm::event::Event::Key(key) = crossterm::event::read()? {
-                 if key.code == crossterm::event::KeyCode::Char('q') {
-                     break;
-                 }
-             }
-         }
+        // Input polling: transport controls (space=pause, ←/→=seek ±5s,
+        // ,/.=frame-step while paused, [/]=speed, o=toggle OSD, s=screenshot),
+        // matching the nihav SDL player's layout.
+        if let Some(control) = poll_transport_control()? {
+            match control {
+                PlayerControl::Quit => break,
+                PlayerControl::TogglePause => {
+                    paused = !paused;
+                    if paused {
+                        audio.pause();
+                    } else {
+                        audio.resume();
+                        start_time = Instant::now()
+                            - Duration::from_secs_f64((frame_idx as f64 / actual_fps as f64) / speed);
+                    }
+                }
+                PlayerControl::Seek(delta) => {
+                    let current = frame_idx as f64 / actual_fps as f64;
+                    let target = (current + delta).max(0.0);
+
+                    source.lock().unwrap().seek(target)?;
+                    frame_buffer.drain();
+                    frame_idx = (target * actual_fps as f64).round() as u64;
+                    start_time = Instant::now() - Duration::from_secs_f64(target / speed);
+                    audio.seek(target);
+                }
+                PlayerControl::StepFrame(delta) if paused => {
+                    let current = frame_idx as f64 / actual_fps as f64;
+                    let target = (current + delta as f64 / actual_fps as f64).max(0.0);
+
+                    source.lock().unwrap().seek(target)?;
+                    frame_buffer.drain();
+                    frame_idx = (target * actual_fps as f64).round() as u64;
+                    audio.seek(target);
+
+                    // Give the reader thread a moment to decode the frame we
+                    // just seeked to, then render it immediately so stepping
+                    // feels responsive even with the main loop paused.
+                    for _ in 0..20 {
+                        if let Some(buffer) = frame_buffer.pop() {
+                            last_frame = Some(buffer.clone());
+                            if !matches!(mode, DisplayMode::Ascii) {
+                                let mut cells = processor.process_frame(&buffer);
+                                overlay.apply(&mut cells, width as usize, target);
+                                display.render_diff(&cells, width as usize)?;
+                            }
+                            break;
+                        }
+                        thread::sleep(Duration::from_millis(10));
+                    }
+                }
+                PlayerControl::StepFrame(_) => {} // no-op while playing
+                PlayerControl::SpeedDown => {
+                    speed = (speed * 0.5).max(0.25);
+                    frame_duration = Duration::from_secs_f64(1.0 / (actual_fps as f64 * speed));
+                    start_time = Instant::now()
+                        - Duration::from_secs_f64((frame_idx as f64 / actual_fps as f64) / speed);
+                }
+                PlayerControl::SpeedUp => {
+                    speed = (speed * 2.0).min(4.0);
+                    frame_duration = Duration::from_secs_f64(1.0 / (actual_fps as f64 * speed));
+                    start_time = Instant::now()
+                        - Duration::from_secs_f64((frame_idx as f64 / actual_fps as f64) / speed);
+                }
+                PlayerControl::ToggleOsd => show_osd = !show_osd,
+                PlayerControl::Screenshot => {
+                    if let Some(frame) = last_frame.as_ref() {
+                        if let Err(e) = write_screenshot(frame, width, height) {
+                            eprintln!("⚠️  Screenshot failed: {}", e);
+                        }
+                    }
+                }
+            }
+        }
+
+        if paused {
+            thread::sleep(Duration::from_millis(50));
+            continue;
+        }
+
+        if waiting {
+            let cached_ms = frame_buffer.queued_frames() as f64 / actual_fps as f64 * 1000.0;
+            if cached_ms < lwm_ms {
+                thread::sleep(Duration::from_millis(10));
+                continue;
+            }
+            waiting = false;
+            audio.resume();
+            start_time = Instant::now() - Duration::from_secs_f64((frame_idx as f64 / actual_fps as f64) / speed);
+        }
 
         // Try to get frame from buffer (non-blocking)
         if let Some(buffer) = frame_buffer.pop() {
-            // ========== PRECISION TIMING SYSTEM ==========
-            // Calculate target time for this frame (nanosecond precision)
-            let target_time = start_time + frame_duration * (frame_idx as u32);
-            let now = Instant::now();
-            
-            // Calculate drift (how far off we are from ideal timing)
-            let drift = if now < target_time {
-                // We're ahead - need to sleep
-                target_time.duration_since(now)
-            } else {
-                // We're behind - no sleep, just track drift
-                Duration::ZERO
-            };
-            
-            // Track maximum drift for diagnostics
-            if drift > max_drift {
-                max_drift = drift;
+            last_frame = Some(buffer.clone());
+            let video_pts = Duration::from_secs_f64(frame_idx as f64 / actual_fps as f64);
+            let current_time = video_pts.as_secs_f64();
+
+            if let Some(end) = end {
+                if current_time > end {
+                    break;
+                }
+            }
+
+            // Fast-forward ranges: advance `fast_idx` past any range we've
+            // already played through, then drop `factor - 1` out of every
+            // `factor` frames while inside the current one, nudging the
+            // audio clock and wall-clock schedule forward to match so
+            // playback doesn't stall "catching up" once the range ends.
+            while fast_idx < fast_ranges.len() && current_time > fast_ranges[fast_idx].end {
+                fast_idx += 1;
+                fast_skip_count = 0;
             }
-            cumulative_drift += drift;
-            
-            // ADAPTIVE SLEEP: Only sleep if drift is significant (>100μs)
-            // This prevents sleeping for tiny amounts which is inaccurate
-            if drift > Duration::from_micros(100) {
-                thread::sleep(drift);
-                total_sleep_time += drift;
+            if fast_idx < fast_ranges.len()
+                && current_time >= fast_ranges[fast_idx].start
+                && current_time <= fast_ranges[fast_idx].end
+            {
+                let factor = fast_ranges[fast_idx].factor;
+                fast_skip_count += 1;
+                if (fast_skip_count as f64) < factor {
+                    frame_idx += 1;
+                    let scaled = frame_idx as f64 / actual_fps as f64;
+                    audio.set_clock(scaled);
+                    start_time = Instant::now() - Duration::from_secs_f64(scaled / speed);
+                    continue;
+                }
+                fast_skip_count = 0;
             }
-            
+
+            if audio_master {
+                // ========== AUDIO-MASTER CLOCK ==========
+                // `av_offset` compensates for a constantly early/late track:
+                // a positive offset means audio is heard later than the clock
+                // reports (e.g. a laggy output device), so we subtract it here
+                // to make video perceive audio as further behind than it
+                // measures, and wait accordingly.
+                let audio_clock = Duration::from_secs_f64((audio.get_clock() - av_offset).max(0.0));
+                let ahead = video_pts > audio_clock;
+                let diff = if ahead { video_pts - audio_clock } else { audio_clock - video_pts };
+
+                if ahead && diff > sync_threshold {
+                    // Video is ahead of audio: wait for audio to catch up.
+                    // Clamp so a single huge gap does a hard resync instead
+                    // of a very long sleep.
+                    thread::sleep(diff.min(hard_resync_threshold));
+                    total_sleep_time += diff;
+                } else if !ahead && diff > hard_resync_threshold {
+                    // Audio jumped far ahead (e.g. after a stall) - hard
+                    // resync instead of dropping hundreds of frames one by one.
+                    frame_idx = (audio_clock.as_secs_f64() * actual_fps as f64).round() as u64;
+                } else if !ahead && diff > sync_threshold {
+                    // Video is behind audio beyond `sync_threshold` - drop
+                    // this frame without rendering and advance to resynchronize.
+                    frame_idx += 1;
+                    if last_fps_report.elapsed() >= Duration::from_secs(2) {
+                        last_fps_report = Instant::now();
+                        frames_since_report = 0;
+                    }
+                    continue;
+                }
+                // else: within threshold, render immediately
+            } else {
+                // ========== WALL-CLOCK FALLBACK (no audio track) ==========
+                let target_time = start_time + frame_duration * (frame_idx as u32);
+                let now = Instant::now();
+
+                if now > target_time + frame_duration {
+                    // Already more than one frame late: decode-and-discard
+                    // (keep the buffer draining and `frame_idx` advancing)
+                    // rather than rendering late and falling further behind.
+                    frame_idx += 1;
+                    continue;
+                }
+
+                let drift = if now < target_time {
+                    target_time.duration_since(now)
+                } else {
+                    Duration::ZERO
+                };
+
+                if drift > max_drift {
+                    max_drift = drift;
+                }
+                cumulative_drift += drift;
+
+                if drift > Duration::from_micros(100) {
+                    thread::sleep(drift);
+                    total_sleep_time += drift;
+                }
+            }
+
             // Record actual frame time
             let frame_start = Instant::now();
 
-            // Render
-            match mode {
-                DisplayMode::Rgb => {
-                    // 1. Process Frame (Parallel Quantization)
-                    let cells = processor.process_frame(&buffer);
-                    // 2. Render Diff (Optimized Output)
-                    display.render_diff(&cells, width as usize)?;
-                },
-                DisplayMode::Ascii => {
-                    // ASCII mode disabled
-                },
+            // Scene-cut / static-frame detection, cheap enough to run every
+            // frame: forces a clean redraw across hard cuts and skips
+            // rendering near-identical frames to save terminal bandwidth.
+            let scene_signal = processor.analyze_frame(&buffer);
+            if scene_signal == crate::core::processor::SceneSignal::Cut {
+                display.force_full_redraw();
+            }
+
+            // Render, unless this is a skipped frame under the adaptive
+            // frame-pacing scheme below (decode-and-discard: `buffer` was
+            // already popped so the ring buffer keeps draining either way).
+            let render_this_frame = frame_idx % skip_factor == 0;
+            if render_this_frame && scene_signal != crate::core::processor::SceneSignal::Static {
+                if render_target != crate::core::render_target::RenderTarget::HalfBlock {
+                    // Pixel graphics protocol: render the raw decoded frame
+                    // directly, skipping FrameProcessor's cell quantization
+                    // (and, with it, the recorder/overlay/diff machinery
+                    // that only makes sense against a cell grid).
+                    display.render_pixels(&buffer, width, height, render_target)?;
+                } else {
+                    match mode {
+                        DisplayMode::Rgb | DisplayMode::Ansi256 | DisplayMode::HalfBlock | DisplayMode::TrueColor => {
+                            // 1. Process Frame (Parallel Quantization)
+                            let mut cells = processor.process_frame(&buffer);
+                            // 1b. Merge any active caption into the cell buffer itself, so it
+                            // participates in the diff below and clears on its own once it
+                            // stops being active.
+                            overlay.apply(&mut cells, width as usize, current_time);
+                            // 2. Render Diff (Optimized Output; Ansi256 quantizes to indexed color internally)
+                            display.render_diff(&cells, width as usize)?;
+                            // 3. Feed the recorder, if one is running
+                            if let Some(recorder) = recorder.as_ref() {
+                                recorder.push_frame(&cells, width as usize);
+                            }
+                        },
+                        DisplayMode::Ascii => {
+                            // ASCII mode disabled
+                        },
+                    }
+                }
+            }
+
+            if show_osd {
+                let current = frame_idx as f64 / actual_fps as f64;
+                let total_secs = source.lock().unwrap().get_duration();
+                let total = total_secs.map(format_timecode).unwrap_or_else(|| "--:--".to_string());
+                let fraction = total_secs.filter(|&t| t > 0.0).map(|t| current / t).unwrap_or(0.0);
+                let osd_text = format!(
+                    " {} / {}  |  {:.1} fps  |  {:.2}x{}",
+                    format_timecode(current),
+                    total,
+                    last_measured_fps,
+                    speed,
+                    if paused { "  [PAUSED]" } else { "" }
+                );
+                display.render_osd(&osd_text, fraction)?;
             }
 
             let frame_end = Instant::now();
             let frame_render_time = frame_end.duration_since(frame_start);
-            
+
+            // Adjust the running render cost estimate and, at most
+            // once a second, the skip factor derived from it. Only rendered
+            // frames count toward the average - a skipped frame's near-zero
+            // cost would otherwise pull the estimate down artificially fast.
+            if render_this_frame {
+                render_cost_ema = update_render_cost_ema(render_cost_ema, frame_render_time.as_secs_f64());
+            }
+            if last_skip_adjust.elapsed() >= Duration::from_secs(1) {
+                last_skip_adjust = Instant::now();
+                let budget = frame_duration.as_secs_f64();
+                skip_factor = adjust_skip_factor(render_cost_ema, budget, skip_factor, MAX_SKIP_FACTOR);
+            }
+
             // Track frame timing
             _last_frame_time = frame_end;
             frame_idx += 1;
@@ -179,13 +625,16 @@ pub fn play_realtime(
             if last_fps_report.elapsed() >= Duration::from_secs(2) {
                 let elapsed = last_fps_report.elapsed().as_secs_f64();
                 let fps_actual = frames_since_report as f64 / elapsed;
-                let buffer_fill = frame_buffer.fill_level();
+                last_measured_fps = fps_actual;
+                let cached_ms = frame_buffer.queued_frames() as f64 / actual_fps as f64 * 1000.0;
+                let buffer_pct = cached_ms * 1005.0 / (hwm_ms * 10.0);
                 let avg_drift = cumulative_drift.as_micros() / frames_since_report as u128;
                 let avg_render = frame_render_time.as_micros();
-                
-                println!("FPS: {:.1}/{} | Buffer: {:.0}% | Drift: {}μs (max: {}μs) | Render: {}μs | Frame: {}", 
-                         fps_actual, fps, 
-                         buffer_fill * 100.0, 
+
+                println!("FPS: {:.1}/{} | Buffer: {:.0}%{} | Drift: {}μs (max: {}μs) | Render: {}μs | Frame: {}",
+                         fps_actual, fps,
+                         buffer_pct,
+                         if waiting { " [Waiting]" } else { "" },
                          avg_drift,
                          max_drift.as_micros(),
                          avg_render,
@@ -197,7 +646,14 @@ pub fn play_realtime(
                 max_drift = Duration::ZERO;
             }
         } else {
-            // Buffer empty - wait briefly
+            // Buffer underrun: rather than hard-skipping frames (which
+            // reads as dropped/stuttered video), freeze the A/V clock and
+            // wait for the reader thread to refill the buffer past the
+            // low-water mark instead.
+            if !waiting {
+                waiting = true;
+                audio.pause();
+            }
             thread::sleep(Duration::from_micros(500));
         }
     }
@@ -206,6 +662,12 @@ pub fn play_realtime(
     running_reader.store(false, Ordering::SeqCst);
     reader_handle.join().ok();
 
+    if let Some(recorder) = recorder {
+        println!("Finalizing recording...");
+        recorder.finish()?;
+    }
+    display.finish_recording()?;
+
     let total_time = start_time.elapsed();
     let expected_time = frame_duration * (frame_idx as u32);
     let final_drift = if total_time > expected_time {
@@ -221,6 +683,78 @@ pub fn play_realtime(
     println!("Final drift: {:.3}s ({:.1}%)", 
              final_drift.as_secs_f64(),
              (final_drift.as_secs_f64() / expected_time.as_secs_f64()) * 100.0);
-    
+
     Ok(())
 }
+
+/// Format a duration in seconds as `mm:ss` for the OSD.
+fn format_timecode(seconds: f64) -> String {
+    let total_secs = seconds.max(0.0).round() as u64;
+    format!("{:02}:{:02}", total_secs / 60, total_secs % 60)
+}
+
+/// Fold a new render-time sample (scene analysis, rendering and OSD for a
+/// frame already decoded by the reader thread) into the running render
+/// cost estimate used by the adaptive frame-pacing skip factor.
+fn update_render_cost_ema(prev_ema: f64, sample: f64) -> f64 {
+    prev_ema * 0.9 + sample * 0.1
+}
+
+/// Adjust the skip factor by at most one step per call, based on how the
+/// running render-cost estimate compares to the per-frame time budget:
+/// ramp up when we're falling behind, ease off once we have plenty of
+/// headroom, and otherwise leave it alone.
+fn adjust_skip_factor(render_cost_ema: f64, budget: f64, skip_factor: u64, max_skip_factor: u64) -> u64 {
+    if render_cost_ema > budget && skip_factor < max_skip_factor {
+        skip_factor + 1
+    } else if render_cost_ema < budget * 0.5 && skip_factor > 1 {
+        skip_factor - 1
+    } else {
+        skip_factor
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn render_cost_ema_converges_toward_sample() {
+        let mut ema = 0.040;
+        for _ in 0..200 {
+            ema = update_render_cost_ema(ema, 0.010);
+        }
+        assert!((ema - 0.010).abs() < 1e-6);
+    }
+
+    #[test]
+    fn render_cost_ema_weights_previous_value_at_ninety_percent() {
+        let ema = update_render_cost_ema(0.040, 0.010);
+        assert!((ema - 0.037).abs() < 1e-9);
+    }
+
+    #[test]
+    fn skip_factor_increases_when_over_budget() {
+        assert_eq!(adjust_skip_factor(0.050, 0.033, 1, 4), 2);
+    }
+
+    #[test]
+    fn skip_factor_stops_at_max() {
+        assert_eq!(adjust_skip_factor(0.050, 0.033, 4, 4), 4);
+    }
+
+    #[test]
+    fn skip_factor_decreases_with_headroom() {
+        assert_eq!(adjust_skip_factor(0.010, 0.033, 2, 4), 1);
+    }
+
+    #[test]
+    fn skip_factor_floor_is_one() {
+        assert_eq!(adjust_skip_factor(0.010, 0.033, 1, 4), 1);
+    }
+
+    #[test]
+    fn skip_factor_holds_steady_in_the_middle_band() {
+        assert_eq!(adjust_skip_factor(0.020, 0.033, 2, 4), 2);
+    }
+}
@@ -1,5 +1,5 @@
 use rayon::prelude::*;
-
+use std::sync::Mutex;
 
 // Represents a single character cell on the terminal
 #[derive(Clone, Copy, PartialEq, Debug)]
@@ -9,14 +9,112 @@ pub struct CellData {
     pub bg: (u8, u8, u8),
 }
 
+// Signature grid used for scene-cut detection: a 32x32 box-averaged luma
+// thumbnail of the incoming frame. Cheap enough to recompute every frame.
+const SIGNATURE_SIZE: usize = 32;
+
+/// What a frame's scene signature implies the renderer should do.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum SceneSignal {
+    /// Frame differs from the previous one by more than the cut threshold -
+    /// likely a hard cut. The renderer should force a full redraw.
+    Cut,
+    /// Frame is nearly identical to the previous one - safe to skip
+    /// rendering entirely and just advance timing.
+    Static,
+    /// Ordinary incremental change; diff-render as usual.
+    Normal,
+}
+
 pub struct FrameProcessor {
     pub width: usize,
     pub height: usize,
+    cut_threshold: f32,
+    skip_threshold: f32,
+    prev_signature: Mutex<Option<[f32; SIGNATURE_SIZE * SIGNATURE_SIZE]>>,
 }
 
 impl FrameProcessor {
     pub fn new(width: usize, height: usize) -> Self {
-        Self { width, height }
+        Self {
+            width,
+            height,
+            cut_threshold: 0.30,
+            skip_threshold: 0.002,
+            prev_signature: Mutex::new(None),
+        }
+    }
+
+    /// Override the default scene-cut / static-frame thresholds.
+    pub fn with_scene_thresholds(mut self, cut_threshold: f32, skip_threshold: f32) -> Self {
+        self.cut_threshold = cut_threshold;
+        self.skip_threshold = skip_threshold;
+        self
+    }
+
+    /// Compute a 32x32 box-averaged luma signature for `frame_data` and
+    /// compare it against the previous frame's signature (normalized mean
+    /// absolute difference over the 0..1 luma range). Updates the stored
+    /// signature as a side effect, so this must be called at most once per
+    /// frame, before `process_frame`.
+    pub fn analyze_frame(&self, frame_data: &[u8]) -> SceneSignal {
+        let mut signature = [0f32; SIGNATURE_SIZE * SIGNATURE_SIZE];
+        let bucket_w = (self.width / SIGNATURE_SIZE).max(1);
+        let bucket_h = (self.height / SIGNATURE_SIZE).max(1);
+
+        for by in 0..SIGNATURE_SIZE {
+            for bx in 0..SIGNATURE_SIZE {
+                let x0 = bx * bucket_w;
+                let y0 = by * bucket_h;
+                let x1 = (x0 + bucket_w).min(self.width);
+                let y1 = (y0 + bucket_h).min(self.height);
+
+                let mut sum = 0u64;
+                let mut count = 0u64;
+                for y in y0..y1 {
+                    for x in x0..x1 {
+                        let idx = (y * self.width + x) * 3;
+                        if idx + 2 < frame_data.len() {
+                            let r = frame_data[idx] as u64;
+                            let g = frame_data[idx + 1] as u64;
+                            let b = frame_data[idx + 2] as u64;
+                            sum += (r * 299 + g * 587 + b * 114) / 1000;
+                            count += 1;
+                        }
+                    }
+                }
+
+                signature[by * SIGNATURE_SIZE + bx] = if count > 0 {
+                    (sum as f32 / count as f32) / 255.0
+                } else {
+                    0.0
+                };
+            }
+        }
+
+        let mut prev = self.prev_signature.lock().unwrap();
+        let signal = match prev.as_ref() {
+            None => SceneSignal::Cut, // First frame: treat as a cut so we force a clean initial redraw
+            Some(previous) => {
+                let diff: f32 = signature
+                    .iter()
+                    .zip(previous.iter())
+                    .map(|(a, b)| (a - b).abs())
+                    .sum::<f32>()
+                    / (SIGNATURE_SIZE * SIGNATURE_SIZE) as f32;
+
+                if diff > self.cut_threshold {
+                    SceneSignal::Cut
+                } else if diff < self.skip_threshold {
+                    SceneSignal::Static
+                } else {
+                    SceneSignal::Normal
+                }
+            }
+        };
+
+        *prev = Some(signature);
+        signal
     }
 
     // Process RGB frame into CellData grid using Half-Block rendering
@@ -0,0 +1,214 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+use crate::core::audio_manager::AudioChannel;
+use crate::core::display_manager::DisplayMode;
+use crate::core::overlay::{Caption, Overlay};
+use crate::core::player;
+
+/// On-disk schema for `Commands::Project`: a full playback session (source
+/// video, render settings, audio, font) captured as TOML so it can be
+/// replayed headlessly, without walking through `run_interactive_mode`'s
+/// `Select` prompts again. Useful for CI/demo reproduction and for sharing a
+/// configured playback.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectFile {
+    pub source: SourceSection,
+    #[serde(default)]
+    pub render: RenderSection,
+    pub audio: Option<AudioSection>,
+    pub font: Option<FontSection>,
+    /// Timed caption overlay, mirroring the external lecture tool's
+    /// `questions = [[start, end, text]]` entries.
+    pub questions: Option<Vec<(f64, f64, String)>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SourceSection {
+    pub files: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RenderSection {
+    #[serde(default = "default_mode")]
+    pub mode: String,
+    #[serde(default = "default_aspect")]
+    pub aspect: String,
+    pub width: Option<u32>,
+    pub height: Option<u32>,
+    #[serde(default)]
+    pub fullscreen: bool,
+    #[serde(default)]
+    pub dither: bool,
+    /// Trim points, kept as strings (not a numeric duration) and parsed by
+    /// `run_project` with the same `player::TimeSpec` ("MM:SS" or plain
+    /// seconds) as the live-playback `--start`/`--end` flags. Fast-forward
+    /// (`--fast`) ranges have no project-file field.
+    pub start: Option<String>,
+    pub end: Option<String>,
+}
+
+impl Default for RenderSection {
+    fn default() -> Self {
+        Self {
+            mode: default_mode(),
+            aspect: default_aspect(),
+            width: None,
+            height: None,
+            fullscreen: false,
+            dither: false,
+            start: None,
+            end: None,
+        }
+    }
+}
+
+fn default_mode() -> String {
+    "rgb".to_string()
+}
+
+fn default_aspect() -> String {
+    "fit".to_string()
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AudioSection {
+    pub path: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FontSection {
+    pub family: String,
+    pub size: f32,
+}
+
+impl ProjectFile {
+    pub fn load(path: &Path) -> Result<Self> {
+        let text = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read project file {}", path.display()))?;
+        toml::from_str(&text)
+            .with_context(|| format!("Failed to parse project file {}", path.display()))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let text = toml::to_string_pretty(self).context("Failed to serialize project file")?;
+        fs::write(path, text).with_context(|| format!("Failed to write project file {}", path.display()))
+    }
+}
+
+pub fn parse_display_mode(s: &str) -> Result<DisplayMode> {
+    match s.to_lowercase().as_str() {
+        "rgb" => Ok(DisplayMode::Rgb),
+        "ascii" => Ok(DisplayMode::Ascii),
+        "ansi256" => Ok(DisplayMode::Ansi256),
+        "halfblock" | "half_block" | "half-block" => Ok(DisplayMode::HalfBlock),
+        "truecolor" | "true_color" | "true-color" => Ok(DisplayMode::TrueColor),
+        other => anyhow::bail!("Unknown render mode '{}' (expected rgb, ascii, ansi256, halfblock, or truecolor)", other),
+    }
+}
+
+pub fn display_mode_name(mode: DisplayMode) -> &'static str {
+    match mode {
+        DisplayMode::Rgb => "rgb",
+        DisplayMode::Ascii => "ascii",
+        DisplayMode::Ansi256 => "ansi256",
+        DisplayMode::HalfBlock => "halfblock",
+        DisplayMode::TrueColor => "truecolor",
+    }
+}
+
+/// Load `path` and drive the same `player::play_realtime` call
+/// `run_interactive_mode` makes, with every choice coming from the TOML
+/// instead of an interactive prompt.
+pub fn run_project(path: &Path) -> Result<()> {
+    let project = ProjectFile::load(path)?;
+    let video_path = project
+        .source
+        .files
+        .first()
+        .context("project file's [source] lists no files")?;
+
+    let mode = parse_display_mode(&project.render.mode)?;
+
+    let (term_cols, term_rows) = crossterm::terminal::size()?;
+    let max_w = (term_cols as u32).saturating_sub(2);
+    let max_h = term_rows as u32 * 2;
+
+    let (mut width, mut height) = if project.render.fullscreen {
+        (max_w, max_h)
+    } else {
+        (
+            project.render.width.unwrap_or(max_w),
+            project.render.height.unwrap_or(max_h),
+        )
+    };
+    // Ensure even dimensions for half-block rendering, same rule
+    // `run_interactive_mode` applies.
+    if width % 2 != 0 {
+        width -= 1;
+    }
+    if height % 2 != 0 {
+        height -= 1;
+    }
+
+    let audio_path = project.audio.as_ref().map(|a| a.path.clone());
+
+    let overlay = match &project.questions {
+        Some(questions) => Overlay::new(
+            questions
+                .iter()
+                .map(|(start, end, text)| Caption { start: *start, end: *end, text: text.clone() })
+                .collect(),
+        ),
+        None => Overlay::default(),
+    };
+
+    let start = project
+        .render
+        .start
+        .as_deref()
+        .map(|s| s.parse::<player::TimeSpec>().map_err(anyhow::Error::msg))
+        .transpose()
+        .context("invalid [render].start in project file")?
+        .map(|t| t.0);
+    let end = project
+        .render
+        .end
+        .as_deref()
+        .map(|s| s.parse::<player::TimeSpec>().map_err(anyhow::Error::msg))
+        .transpose()
+        .context("invalid [render].end in project file")?
+        .map(|t| t.0);
+
+    player::play_realtime(
+        video_path,
+        audio_path.as_deref(),
+        width,
+        height,
+        0, // 0 means native fps
+        mode,
+        None,
+        AudioChannel::Stereo,
+        if project.render.dither {
+            crate::core::display_manager::DitherMode::ErrorDiffusion
+        } else {
+            crate::core::display_manager::DitherMode::None
+        },
+        true,
+        start,
+        end,
+        Vec::new(),
+        overlay,
+        false, // mute
+        0.0,   // av_offset
+        None,  // record_cast
+        crate::core::video_decoder::DecodeBackend::Auto,
+        None,  // record_cells
+        false, // use_stdin
+        crate::core::render_target::RenderTarget::HalfBlock,
+    )?;
+
+    Ok(())
+}
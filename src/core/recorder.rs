@@ -0,0 +1,127 @@
+use anyhow::{Context, Result};
+use crossbeam_channel::{bounded, Sender, TrySendError};
+use std::io::Write;
+use std::process::{Child, Command, Stdio};
+use std::thread::JoinHandle;
+
+use crate::core::processor::CellData;
+
+/// Records the exact rendered cell buffer to a video file by piping packed
+/// RGB24 frames into an FFmpeg child process, mirroring how the rest of the
+/// project already shells out to FFmpeg for audio extraction rather than
+/// vendoring an encoder crate.
+///
+/// Frames are handed to a dedicated encode thread through a small bounded
+/// channel. `push_frame` is non-blocking: if the encoder falls behind, the
+/// frame is dropped instead of stalling the playback loop, since a dropped
+/// frame in the recording is far cheaper than a stutter in the terminal.
+pub struct Recorder {
+    tx: Sender<Vec<u8>>,
+    handle: Option<JoinHandle<Result<()>>>,
+    pixel_width: usize,
+    pixel_height: usize,
+}
+
+impl Recorder {
+    /// `width`/`height` are the terminal cell-grid dimensions; the encoded
+    /// video resolution is derived from them using the half-block glyph
+    /// aspect (1 cell = 1 pixel wide, 2 pixels tall).
+    pub fn start(out_path: &str, width: usize, height: usize, fps: f32, audio_path: Option<&str>) -> Result<Self> {
+        let pixel_width = width;
+        let pixel_height = height * 2;
+
+        let mut cmd = Command::new("ffmpeg");
+        cmd.arg("-y")
+            .arg("-f").arg("rawvideo")
+            .arg("-pix_fmt").arg("rgb24")
+            .arg("-s").arg(format!("{}x{}", pixel_width, pixel_height))
+            .arg("-r").arg(format!("{:.3}", fps))
+            .arg("-i").arg("-");
+
+        if let Some(audio) = audio_path {
+            cmd.arg("-i").arg(audio).arg("-c:a").arg("aac").arg("-shortest");
+        }
+
+        if out_path.to_lowercase().ends_with(".gif") {
+            cmd.arg("-vf").arg("split[s0][s1];[s0]palettegen[p];[s1][p]paletteuse");
+        } else {
+            cmd.arg("-c:v").arg("libx264").arg("-pix_fmt").arg("yuv420p");
+        }
+
+        cmd.arg(out_path)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null());
+
+        let mut child: Child = cmd.spawn().context("Failed to spawn ffmpeg for recording")?;
+        let mut stdin = child.stdin.take().context("Failed to open ffmpeg stdin")?;
+
+        // Buffer capacity of 1 second of frames; deep enough to absorb a
+        // brief encoder stall without the push-side blocking.
+        let (tx, rx) = bounded::<Vec<u8>>(fps.max(1.0).round() as usize);
+
+        let handle = std::thread::spawn(move || -> Result<()> {
+            while let Ok(frame) = rx.recv() {
+                stdin.write_all(&frame)?;
+            }
+            drop(stdin);
+            let status = child.wait()?;
+            if !status.success() {
+                anyhow::bail!("ffmpeg exited with status {}", status);
+            }
+            Ok(())
+        });
+
+        Ok(Self {
+            tx,
+            handle: Some(handle),
+            pixel_width,
+            pixel_height,
+        })
+    }
+
+    /// Convert the rendered half-block cell grid back into a packed RGB24
+    /// frame and enqueue it for encoding. Drops the frame if the encoder
+    /// thread is still busy with a previous one.
+    pub fn push_frame(&self, cells: &[CellData], cell_width: usize) {
+        let mut frame = vec![0u8; self.pixel_width * self.pixel_height * 3];
+
+        for (i, cell) in cells.iter().enumerate() {
+            let cx = i % cell_width;
+            let cy = i / cell_width;
+            let top_row = cy * 2;
+            let bottom_row = cy * 2 + 1;
+
+            let top_off = (top_row * self.pixel_width + cx) * 3;
+            frame[top_off] = cell.fg.0;
+            frame[top_off + 1] = cell.fg.1;
+            frame[top_off + 2] = cell.fg.2;
+
+            let bottom_off = (bottom_row * self.pixel_width + cx) * 3;
+            frame[bottom_off] = cell.bg.0;
+            frame[bottom_off + 1] = cell.bg.1;
+            frame[bottom_off + 2] = cell.bg.2;
+        }
+
+        match self.tx.try_send(frame) {
+            Ok(_) => {}
+            Err(TrySendError::Full(_)) => {
+                // Encoder thread is behind; drop this frame rather than
+                // stalling playback.
+            }
+            Err(TrySendError::Disconnected(_)) => {
+                // Encoder thread died; nothing more we can do here.
+            }
+        }
+    }
+
+    /// Flush remaining frames, close the FFmpeg pipe, and wait for the
+    /// encode to finish.
+    pub fn finish(mut self) -> Result<()> {
+        drop(self.tx);
+        if let Some(handle) = self.handle.take() {
+            handle.join().map_err(|_| anyhow::anyhow!("Recorder encode thread panicked"))??;
+        }
+        Ok(())
+    }
+}
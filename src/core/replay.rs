@@ -0,0 +1,356 @@
+use anyhow::{bail, Context, Result};
+use std::io::{Read, Seek, SeekFrom, Write};
+
+use crate::core::processor::CellData;
+
+/// 16-byte header: magic(4) + width(4) + height(4) + frame_count(4), all
+/// integers little-endian. `width`/`height` are in terminal cells, matching
+/// the grid `FrameProcessor::process_frame` produces.
+const MAGIC: &[u8; 4] = b"GQOI";
+const HEADER_LEN: u64 = 16;
+
+/// Marks the end of one frame's op stream, the same byte pattern QOI uses
+/// to terminate a whole image. Any pending run is flushed before it is
+/// written. The reader decodes exactly `width * height` cells and then
+/// expects these bytes, so a mismatch flags a corrupt or misaligned stream;
+/// the marker is not used to find frame boundaries, since op bytes can
+/// produce the same pattern.
+const FRAME_END_MARKER: [u8; 8] = [0, 0, 0, 0, 0, 0, 0, 1];
+
+const OP_INDEX: u8 = 0b0000_0000;
+const OP_DIFF: u8 = 0b0100_0000;
+const OP_RUN: u8 = 0b1000_0000;
+const OP_FULL: u8 = 0b1111_1111;
+const TAG_MASK: u8 = 0b1100_0000;
+const MAX_RUN: u8 = 64;
+
+fn cell_hash(cell: &CellData) -> usize {
+    let (fr, fg, fb) = cell.fg;
+    let (br, _bg, _bb) = cell.bg;
+    let h = (cell.char as u32)
+        .wrapping_mul(3)
+        .wrapping_add(fr as u32 * 5)
+        .wrapping_add(fg as u32 * 7)
+        .wrapping_add(fb as u32 * 11)
+        .wrapping_add(br as u32 * 13);
+    (h % 64) as usize
+}
+
+/// Pack a single channel delta (clamped to -2..=1) into 2 bits, biased like
+/// QOI's OP_DIFF so the on-wire value is always in 0..=3.
+fn pack_delta(from: u8, to: u8) -> Option<u8> {
+    let d = to as i16 - from as i16;
+    if (-2..=1).contains(&d) {
+        Some((d + 2) as u8)
+    } else {
+        None
+    }
+}
+
+fn unpack_delta(from: u8, packed: u8) -> u8 {
+    (from as i16 + packed as i16 - 2) as u8
+}
+
+/// Encodes a stream of `CellData` frames (all sharing one `width`x`height`)
+/// into the compact QOI-style delta format and writes it to `W`. `W` must
+/// be seekable so `finish` can go back and patch in the final frame count.
+pub struct CellStreamWriter<W: Write + Seek> {
+    writer: W,
+    width: usize,
+    height: usize,
+    frame_count: u32,
+    prev: CellData,
+    run: u8,
+    index_cache: [CellData; 64],
+}
+
+impl<W: Write + Seek> CellStreamWriter<W> {
+    pub fn new(mut writer: W, width: usize, height: usize) -> Result<Self> {
+        writer.write_all(MAGIC)?;
+        writer.write_all(&(width as u32).to_le_bytes())?;
+        writer.write_all(&(height as u32).to_le_bytes())?;
+        writer.write_all(&0u32.to_le_bytes())?; // frame_count, patched in `finish`
+
+        Ok(Self {
+            writer,
+            width,
+            height,
+            frame_count: 0,
+            prev: CellData { char: ' ', fg: (0, 0, 0), bg: (0, 0, 0) },
+            run: 0,
+            index_cache: [CellData { char: ' ', fg: (0, 0, 0), bg: (0, 0, 0) }; 64],
+        })
+    }
+
+    /// Encode and write one frame's grid of cells.
+    pub fn push_frame(&mut self, cells: &[CellData]) -> Result<()> {
+        if cells.len() != self.width * self.height {
+            bail!(
+                "frame has {} cells, expected {}x{}={}",
+                cells.len(),
+                self.width,
+                self.height,
+                self.width * self.height
+            );
+        }
+
+        for cell in cells {
+            self.encode_cell(cell)?;
+        }
+        self.flush_run()?;
+        self.writer.write_all(&FRAME_END_MARKER)?;
+        self.frame_count += 1;
+
+        Ok(())
+    }
+
+    fn encode_cell(&mut self, cell: &CellData) -> Result<()> {
+        if *cell == self.prev {
+            self.run += 1;
+            if self.run == MAX_RUN {
+                self.flush_run()?;
+            }
+            return Ok(());
+        }
+        self.flush_run()?;
+
+        let idx = cell_hash(cell);
+        if self.index_cache[idx] == *cell {
+            self.writer.write_all(&[OP_INDEX | idx as u8])?;
+            self.prev = *cell;
+            return Ok(());
+        }
+        self.index_cache[idx] = *cell;
+
+        if cell.char == self.prev.char {
+            if let (Some(fg_r), Some(fg_g), Some(fg_b), Some(bg_r), Some(bg_g), Some(bg_b)) = (
+                pack_delta(self.prev.fg.0, cell.fg.0),
+                pack_delta(self.prev.fg.1, cell.fg.1),
+                pack_delta(self.prev.fg.2, cell.fg.2),
+                pack_delta(self.prev.bg.0, cell.bg.0),
+                pack_delta(self.prev.bg.1, cell.bg.1),
+                pack_delta(self.prev.bg.2, cell.bg.2),
+            ) {
+                let fg_byte = OP_DIFF | (fg_r << 4) | (fg_g << 2) | fg_b;
+                let bg_byte = (bg_r << 4) | (bg_g << 2) | bg_b;
+                self.writer.write_all(&[fg_byte, bg_byte])?;
+                self.prev = *cell;
+                return Ok(());
+            }
+        }
+
+        let mut char_buf = [0u8; 4];
+        let char_bytes = cell.char.encode_utf8(&mut char_buf).as_bytes();
+        self.writer.write_all(&[OP_FULL, char_bytes.len() as u8])?;
+        self.writer.write_all(char_bytes)?;
+        self.writer
+            .write_all(&[cell.fg.0, cell.fg.1, cell.fg.2, cell.bg.0, cell.bg.1, cell.bg.2])?;
+        self.prev = *cell;
+
+        Ok(())
+    }
+
+    fn flush_run(&mut self) -> Result<()> {
+        if self.run > 0 {
+            self.writer.write_all(&[OP_RUN | (self.run - 1)])?;
+            self.run = 0;
+        }
+        Ok(())
+    }
+
+    /// Seek back and patch in the final frame count, then flush.
+    pub fn finish(mut self) -> Result<()> {
+        self.writer.seek(SeekFrom::Start(12))?;
+        self.writer.write_all(&self.frame_count.to_le_bytes())?;
+        self.writer.flush()?;
+        Ok(())
+    }
+}
+
+/// Reads back a stream written by `CellStreamWriter`, reconstructing one
+/// `Vec<CellData>` grid per frame.
+pub struct CellStreamReader<R: Read> {
+    reader: R,
+    pub width: usize,
+    pub height: usize,
+    pub frame_count: u32,
+    prev: CellData,
+    run_remaining: u8,
+    index_cache: [CellData; 64],
+}
+
+impl<R: Read> CellStreamReader<R> {
+    pub fn open(mut reader: R) -> Result<Self> {
+        let mut header = [0u8; HEADER_LEN as usize];
+        reader.read_exact(&mut header).context("failed to read cell stream header")?;
+
+        if &header[0..4] != MAGIC {
+            bail!("not a cell replay stream (bad magic)");
+        }
+        let width = u32::from_le_bytes(header[4..8].try_into().unwrap()) as usize;
+        let height = u32::from_le_bytes(header[8..12].try_into().unwrap()) as usize;
+        let frame_count = u32::from_le_bytes(header[12..16].try_into().unwrap());
+
+        Ok(Self {
+            reader,
+            width,
+            height,
+            frame_count,
+            prev: CellData { char: ' ', fg: (0, 0, 0), bg: (0, 0, 0) },
+            run_remaining: 0,
+            index_cache: [CellData { char: ' ', fg: (0, 0, 0), bg: (0, 0, 0) }; 64],
+        })
+    }
+
+    /// Read and decode the next frame. Returns `Ok(None)` at a clean EOF
+    /// between frames.
+    pub fn read_frame(&mut self) -> Result<Option<Vec<CellData>>> {
+        let total = self.width * self.height;
+        let mut cells = Vec::with_capacity(total);
+
+        while cells.len() < total {
+            if self.run_remaining > 0 {
+                self.run_remaining -= 1;
+                cells.push(self.prev);
+                continue;
+            }
+
+            let mut tag_byte = [0u8; 1];
+            match self.reader.read_exact(&mut tag_byte) {
+                Ok(()) => {}
+                Err(e) if e.kind() == std::io::ErrorKind::UnexpectedEof && cells.is_empty() => {
+                    return Ok(None);
+                }
+                Err(e) => return Err(e.into()),
+            }
+            let tag = tag_byte[0];
+
+            let cell = if tag == OP_FULL {
+                let mut len_buf = [0u8; 1];
+                self.reader.read_exact(&mut len_buf)?;
+                let mut char_buf = [0u8; 4];
+                self.reader.read_exact(&mut char_buf[..len_buf[0] as usize])?;
+                let ch = std::str::from_utf8(&char_buf[..len_buf[0] as usize])
+                    .ok()
+                    .and_then(|s| s.chars().next())
+                    .context("invalid UTF-8 char literal in cell stream")?;
+                let mut rgb = [0u8; 6];
+                self.reader.read_exact(&mut rgb)?;
+                CellData { char: ch, fg: (rgb[0], rgb[1], rgb[2]), bg: (rgb[3], rgb[4], rgb[5]) }
+            } else {
+                match tag & TAG_MASK {
+                    OP_INDEX => self.index_cache[(tag & !TAG_MASK) as usize],
+                    OP_DIFF => {
+                        let mut bg_byte = [0u8; 1];
+                        self.reader.read_exact(&mut bg_byte)?;
+                        let fg_bits = tag & !TAG_MASK;
+                        let bg_bits = bg_byte[0] & !TAG_MASK;
+                        CellData {
+                            char: self.prev.char,
+                            fg: (
+                                unpack_delta(self.prev.fg.0, (fg_bits >> 4) & 0b11),
+                                unpack_delta(self.prev.fg.1, (fg_bits >> 2) & 0b11),
+                                unpack_delta(self.prev.fg.2, fg_bits & 0b11),
+                            ),
+                            bg: (
+                                unpack_delta(self.prev.bg.0, (bg_bits >> 4) & 0b11),
+                                unpack_delta(self.prev.bg.1, (bg_bits >> 2) & 0b11),
+                                unpack_delta(self.prev.bg.2, bg_bits & 0b11),
+                            ),
+                        }
+                    }
+                    OP_RUN => {
+                        // Tag stores `run_len - 1`; this cell accounts for
+                        // one occurrence, the rest trickle out of the
+                        // `run_remaining > 0` branch above on later iterations.
+                        self.run_remaining = tag & !TAG_MASK;
+                        self.prev
+                    }
+                    _ => bail!("unrecognized cell stream op byte: {:#04x}", tag),
+                }
+            };
+
+            let idx = cell_hash(&cell);
+            self.index_cache[idx] = cell;
+            self.prev = cell;
+            cells.push(cell);
+        }
+
+        let mut marker = [0u8; FRAME_END_MARKER.len()];
+        self.reader.read_exact(&mut marker)?;
+        if marker != FRAME_END_MARKER {
+            bail!("missing frame end marker in cell stream");
+        }
+
+        Ok(Some(cells))
+    }
+}
+
+/// Stream a `CellStreamWriter` recording back to the terminal at a fixed
+/// `fps`, reproducing the exact fg/bg/char values captured at record time.
+/// Always renders in `DisplayMode::Rgb` regardless of the mode active when
+/// the recording was made, since `render_diff`'s truecolor path writes
+/// `CellData`'s colors through unchanged - the only mode that replays a
+/// captured stream byte-for-byte rather than re-quantizing it.
+pub fn play_cells(path: &str, fps: u32) -> Result<()> {
+    let file = std::fs::File::open(path).with_context(|| format!("Failed to open cell replay file {}", path))?;
+    let mut reader = CellStreamReader::open(file)?;
+
+    let mut display = crate::core::display_manager::DisplayManager::new(
+        crate::core::display_manager::DisplayMode::Rgb,
+    )?;
+    let frame_duration = std::time::Duration::from_secs_f64(1.0 / fps.max(1) as f64);
+
+    while let Some(cells) = reader.read_frame()? {
+        let start = std::time::Instant::now();
+        display.render_diff(&cells, reader.width)?;
+        if let Some(remaining) = frame_duration.checked_sub(start.elapsed()) {
+            std::thread::sleep(remaining);
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    fn sample_frames() -> Vec<Vec<CellData>> {
+        let a = CellData { char: '▀', fg: (255, 0, 0), bg: (0, 255, 0) };
+        let b = CellData { char: '▀', fg: (253, 1, 0), bg: (0, 254, 0) }; // small diff from `a`
+        let c = CellData { char: '#', fg: (10, 20, 30), bg: (40, 50, 60) }; // unrelated literal
+
+        vec![
+            vec![a, a, a, a], // OP_FULL then OP_RUN
+            vec![b, b, c, a], // OP_DIFF, OP_RUN, OP_FULL, OP_INDEX
+        ]
+    }
+
+    #[test]
+    fn test_roundtrip_encode_decode() {
+        let frames = sample_frames();
+        let mut buf = Cursor::new(Vec::new());
+
+        {
+            let mut writer = CellStreamWriter::new(&mut buf, 2, 2).unwrap();
+            for frame in &frames {
+                writer.push_frame(frame).unwrap();
+            }
+            writer.finish().unwrap();
+        }
+
+        buf.set_position(0);
+        let mut reader = CellStreamReader::open(buf).unwrap();
+        assert_eq!(reader.width, 2);
+        assert_eq!(reader.height, 2);
+        assert_eq!(reader.frame_count, frames.len() as u32);
+
+        for expected in &frames {
+            let decoded = reader.read_frame().unwrap().expect("frame present");
+            assert_eq!(&decoded, expected);
+        }
+        assert!(reader.read_frame().unwrap().is_none());
+    }
+}
@@ -8,6 +8,47 @@ use opencv::{
 use std::fs::OpenOptions;
 use std::io::Write;
 
+/// Which `VideoCapture` backend API (and, where it applies, hardware
+/// acceleration device) to request when opening a video. `Auto` leaves the
+/// choice to OpenCV (`CAP_ANY`); the others force a specific platform
+/// backend - mainly so constrained devices like a Raspberry Pi can be
+/// pointed at their hardware decoder (`V4l2m2m`) instead of silently
+/// falling back to an unaccelerated software path that can't keep up with
+/// real-time playback.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Default, clap::ValueEnum)]
+pub enum DecodeBackend {
+    #[default]
+    Auto,
+    /// macOS AVFoundation, with VideoToolbox GPU decode.
+    Videotoolbox,
+    /// Windows Media Foundation GPU decode.
+    Mediafoundation,
+    /// Linux V4L2 with the memory-to-memory (M2M) hardware codec path - the
+    /// accelerated path small boards need to keep up in real time.
+    V4l2m2m,
+    /// Force CPU-only decode: no hardware acceleration is requested, even
+    /// if one would otherwise be picked automatically.
+    Software,
+}
+
+impl DecodeBackend {
+    fn capture_api(self) -> i32 {
+        match self {
+            DecodeBackend::Auto | DecodeBackend::Software => videoio::CAP_ANY,
+            DecodeBackend::Videotoolbox => videoio::CAP_AVFOUNDATION,
+            DecodeBackend::Mediafoundation => videoio::CAP_MSMF,
+            DecodeBackend::V4l2m2m => videoio::CAP_V4L2,
+        }
+    }
+
+    fn hw_acceleration(self) -> i32 {
+        match self {
+            DecodeBackend::Software => videoio::VIDEO_ACCELERATION_NONE,
+            _ => videoio::VIDEO_ACCELERATION_ANY,
+        }
+    }
+}
+
 pub struct VideoDecoder {
     capture: videoio::VideoCapture,
     width: u32,
@@ -17,7 +58,7 @@ pub struct VideoDecoder {
 }
 
 impl VideoDecoder {
-    pub fn new(path: &str, width: u32, height: u32) -> Result<Self> {
+    pub fn new(path: &str, width: u32, height: u32, backend: DecodeBackend) -> Result<Self> {
         // Setup logging with absolute path
         let mut log_path = std::env::current_dir()?;
         log_path.push("debug.log");
@@ -38,35 +79,51 @@ impl VideoDecoder {
             writeln!(log_file, "DEBUG: Detected 3D SBS video - crop enabled (left half)")?;
         }
         
-        writeln!(log_file, "DEBUG: Opening video with OpenCV...")?;
-        
-        // CAP_ANY allows OpenCV to choose the best backend
-        // macOS: AVFoundation (VideoToolbox GPU decode)
-        // Windows: Media Foundation (GPU decode)
-        // Linux: V4L2/GStreamer
-        let mut capture = videoio::VideoCapture::from_file(path, videoio::CAP_ANY)?;
-        
-        // Try to enforce HW acceleration
-        // Note: This might not work on all backends/platforms, but it's worth setting
-        let _ = capture.set(videoio::CAP_PROP_HW_ACCELERATION, videoio::VIDEO_ACCELERATION_ANY as f64);
-        
+        writeln!(log_file, "DEBUG: Opening video with OpenCV (requested backend: {:?})...", backend)?;
+
+        let mut capture = videoio::VideoCapture::from_file(path, backend.capture_api())?;
+
+        // Graceful fallback: a platform-specific backend (e.g.
+        // V4l2m2m on a non-Linux box) simply won't open, so retry with
+        // CAP_ANY rather than failing the whole decoder over it.
+        if !capture.is_opened().unwrap_or(false) && backend != DecodeBackend::Auto {
+            writeln!(log_file, "WARN: Backend {:?} failed to open; falling back to CAP_ANY", backend)?;
+            capture = videoio::VideoCapture::from_file(path, videoio::CAP_ANY)?;
+        }
+
         if !capture.is_opened()? {
             let err_msg = format!("Failed to open video file: {}", path);
             writeln!(log_file, "ERROR: {}", err_msg)?;
             return Err(anyhow!(err_msg));
         }
 
+        // Request HW acceleration (and, when V4l2m2m was requested, an
+        // explicit device) per the requested `backend`, on whichever
+        // capture opened above, including the CAP_ANY fallback. Both are
+        // best-effort: unsupported combinations are silently ignored by
+        // OpenCV, which is why the negotiated value is read back and
+        // logged below instead of just trusting what was requested.
+        let _ = capture.set(videoio::CAP_PROP_HW_ACCELERATION, backend.hw_acceleration() as f64);
+        if backend == DecodeBackend::V4l2m2m {
+            let _ = capture.set(videoio::CAP_PROP_HW_DEVICE, 0.0);
+        }
+        let negotiated_accel = capture.get(videoio::CAP_PROP_HW_ACCELERATION).unwrap_or(-1.0);
+
         let fps = capture.get(videoio::CAP_PROP_FPS)?;
         let orig_width = capture.get(videoio::CAP_PROP_FRAME_WIDTH)? as u32;
         let orig_height = capture.get(videoio::CAP_PROP_FRAME_HEIGHT)? as u32;
-        
+
         writeln!(log_file, "SUCCESS: OpenCV VideoCapture opened")?;
         writeln!(log_file, "  Original: {}x{}", orig_width, orig_height)?;
         writeln!(log_file, "  FPS: {}", fps)?;
-        writeln!(log_file, "  Backend: AVFoundation (GPU decode)")?;
+        writeln!(log_file, "  Requested backend: {:?}", backend)?;
+        writeln!(log_file, "  Negotiated HW acceleration code: {}", negotiated_accel)?;
         writeln!(log_file, "=========================")?;
-        
-        println!("DEBUG: OpenCV VideoCapture opened successfully. Detected FPS: {}", fps);
+
+        println!(
+            "DEBUG: OpenCV VideoCapture opened successfully (backend: {:?}, hw_accel: {}). Detected FPS: {}",
+            backend, negotiated_accel, fps
+        );
 
         Ok(Self {
             capture,
@@ -81,6 +138,24 @@ impl VideoDecoder {
         self.fps
     }
 
+    /// Total duration of the video in seconds, derived from the frame count
+    /// reported by OpenCV. Returns `None` if the backend can't report it
+    /// (e.g. some streamed sources).
+    pub fn get_duration(&self) -> Option<f64> {
+        let frame_count = self.capture.get(videoio::CAP_PROP_FRAME_COUNT).ok()?;
+        if frame_count <= 0.0 || self.fps <= 0.0 {
+            return None;
+        }
+        Some(frame_count / self.fps)
+    }
+
+    /// Seek to an absolute position in the video, in seconds.
+    pub fn seek(&mut self, seconds: f64) -> Result<()> {
+        let target_frame = (seconds.max(0.0) * self.fps).round();
+        self.capture.set(videoio::CAP_PROP_POS_FRAMES, target_frame)?;
+        Ok(())
+    }
+
     pub fn read_frame(&mut self) -> Result<Option<Vec<u8>>> {
         let start_total = std::time::Instant::now();
         let mut frame = Mat::default();
@@ -0,0 +1,55 @@
+use anyhow::Result;
+use std::io::Read;
+
+/// Common surface both video decoders expose to the playback loop: a
+/// frame rate and a way to pull the next decoded frame as an interleaved
+/// RGB buffer sized `width * height * 3`, already resized/letterboxed to
+/// the target dimensions. Lets callers pick `VideoDecoder` (OpenCV) or
+/// `Y4mDecoder` (a raw `ffmpeg ... -f yuv4mpegpipe -` pipe) without caring
+/// which one is actually decoding.
+pub trait VideoSource {
+    /// Frames per second, as reported by the source.
+    fn get_fps(&self) -> f64;
+
+    /// Decode and return the next frame, or `None` at end of stream.
+    fn read_frame(&mut self) -> Result<Option<Vec<u8>>>;
+
+    /// Seek to an absolute position in seconds. Sources that can't seek
+    /// (e.g. a live stdin pipe) report that instead of silently no-oping.
+    fn seek(&mut self, _seconds: f64) -> Result<()> {
+        Err(anyhow::anyhow!("this video source does not support seeking"))
+    }
+
+    /// Total duration in seconds, if the source can report one.
+    fn get_duration(&self) -> Option<f64> {
+        None
+    }
+}
+
+impl VideoSource for crate::core::video_decoder::VideoDecoder {
+    fn get_fps(&self) -> f64 {
+        self.get_fps()
+    }
+
+    fn read_frame(&mut self) -> Result<Option<Vec<u8>>> {
+        self.read_frame()
+    }
+
+    fn seek(&mut self, seconds: f64) -> Result<()> {
+        self.seek(seconds)
+    }
+
+    fn get_duration(&self) -> Option<f64> {
+        self.get_duration()
+    }
+}
+
+impl<R: Read> VideoSource for crate::core::y4m_decoder::Y4mDecoder<R> {
+    fn get_fps(&self) -> f64 {
+        self.get_fps()
+    }
+
+    fn read_frame(&mut self) -> Result<Option<Vec<u8>>> {
+        self.read_frame()
+    }
+}
@@ -0,0 +1,165 @@
+use anyhow::{anyhow, Context, Result};
+use std::io::{BufRead, BufReader, Read};
+
+/// Decodes a YUV4MPEG2 (`.y4m`) stream - the format `ffmpeg -f yuv4mpegpipe -`
+/// produces - without linking OpenCV. Only the `C420` (4:2:0 planar)
+/// colorspace is supported, which is what ffmpeg emits by default.
+///
+/// Frames are letterboxed into `width x height` the same way
+/// `VideoDecoder` does: scale to fit preserving aspect ratio, then center
+/// on a black canvas, so both decoders hand `FrameProcessor` the same shape
+/// of buffer regardless of which one produced it.
+pub struct Y4mDecoder<R: Read> {
+    reader: BufReader<R>,
+    source_width: u32,
+    source_height: u32,
+    fps: f64,
+    width: u32,
+    height: u32,
+}
+
+impl Y4mDecoder<std::io::Stdin> {
+    /// Open a Y4M stream on stdin, e.g. fed by
+    /// `ffmpeg -i in.mkv -f yuv4mpegpipe - | gascii`.
+    pub fn from_stdin(width: u32, height: u32) -> Result<Self> {
+        Self::new(std::io::stdin(), width, height)
+    }
+}
+
+impl<R: Read> Y4mDecoder<R> {
+    pub fn new(reader: R, width: u32, height: u32) -> Result<Self> {
+        let mut reader = BufReader::new(reader);
+
+        let mut header = String::new();
+        reader.read_line(&mut header).context("Failed to read Y4M header")?;
+        let header = header.trim_end();
+
+        let mut tokens = header.split_ascii_whitespace();
+        if tokens.next() != Some("YUV4MPEG2") {
+            anyhow::bail!("Not a YUV4MPEG2 stream (got header: '{}')", header);
+        }
+
+        let mut source_width = None;
+        let mut source_height = None;
+        let mut fps = None;
+        let mut colorspace = "420".to_string();
+
+        for tag in tokens {
+            let (kind, value) = tag.split_at(1);
+            match kind {
+                "W" => source_width = Some(value.parse::<u32>().context("invalid Y4M width tag")?),
+                "H" => source_height = Some(value.parse::<u32>().context("invalid Y4M height tag")?),
+                "F" => {
+                    let (num, den) = value.split_once(':').context("invalid Y4M framerate tag")?;
+                    let num: f64 = num.parse().context("invalid Y4M framerate numerator")?;
+                    let den: f64 = den.parse().context("invalid Y4M framerate denominator")?;
+                    fps = Some(if den > 0.0 { num / den } else { 0.0 });
+                }
+                "C" => colorspace = value.to_string(),
+                // I (interlacing), A (pixel aspect ratio), X (comment) and
+                // anything else are accepted but not needed to decode frames.
+                _ => {}
+            }
+        }
+
+        if !colorspace.starts_with("420") {
+            anyhow::bail!("Unsupported Y4M colorspace 'C{}' (only C420* is supported)", colorspace);
+        }
+
+        let source_width = source_width.context("Y4M header missing W<width> tag")?;
+        let source_height = source_height.context("Y4M header missing H<height> tag")?;
+        let fps = fps.unwrap_or(30.0);
+
+        Ok(Self { reader, source_width, source_height, fps, width, height })
+    }
+
+    pub fn get_fps(&self) -> f64 {
+        self.fps
+    }
+
+    /// Read one `FRAME[params]\n` marker followed by a planar 4:2:0 frame
+    /// (a `source_width x source_height` Y plane, then two
+    /// `source_width/2 x source_height/2` U/V planes), and return it
+    /// converted to RGB and letterboxed into `width x height`.
+    pub fn read_frame(&mut self) -> Result<Option<Vec<u8>>> {
+        let mut marker = String::new();
+        let bytes_read = self.reader.read_line(&mut marker).context("Failed to read Y4M frame marker")?;
+        if bytes_read == 0 {
+            return Ok(None); // EOF
+        }
+        if !marker.starts_with("FRAME") {
+            return Err(anyhow!("Expected Y4M 'FRAME' marker, got '{}'", marker.trim_end()));
+        }
+
+        let w = self.source_width as usize;
+        let h = self.source_height as usize;
+        let cw = w.div_ceil(2);
+        let ch = h.div_ceil(2);
+
+        let mut y_plane = vec![0u8; w * h];
+        let mut u_plane = vec![0u8; cw * ch];
+        let mut v_plane = vec![0u8; cw * ch];
+
+        if self.reader.read_exact(&mut y_plane).is_err() {
+            return Ok(None); // Truncated stream at EOF
+        }
+        self.reader.read_exact(&mut u_plane).context("Truncated Y4M U plane")?;
+        self.reader.read_exact(&mut v_plane).context("Truncated Y4M V plane")?;
+
+        let rgb = yuv420_to_rgb(&y_plane, &u_plane, &v_plane, w, h);
+        Ok(Some(letterbox_rgb(&rgb, w, h, self.width as usize, self.height as usize)))
+    }
+}
+
+/// BT.601 full-range YUV -> RGB conversion of a planar 4:2:0 frame into an
+/// interleaved `w * h * 3` RGB buffer.
+fn yuv420_to_rgb(y_plane: &[u8], u_plane: &[u8], v_plane: &[u8], w: usize, h: usize) -> Vec<u8> {
+    let cw = w.div_ceil(2);
+    let mut rgb = vec![0u8; w * h * 3];
+
+    for y in 0..h {
+        for x in 0..w {
+            let yy = y_plane[y * w + x] as f32;
+            let cu = u_plane[(y / 2) * cw + x / 2] as f32 - 128.0;
+            let cv = v_plane[(y / 2) * cw + x / 2] as f32 - 128.0;
+
+            let r = yy + 1.402 * cv;
+            let g = yy - 0.344136 * cu - 0.714136 * cv;
+            let b = yy + 1.772 * cu;
+
+            let idx = (y * w + x) * 3;
+            rgb[idx] = r.clamp(0.0, 255.0) as u8;
+            rgb[idx + 1] = g.clamp(0.0, 255.0) as u8;
+            rgb[idx + 2] = b.clamp(0.0, 255.0) as u8;
+        }
+    }
+
+    rgb
+}
+
+/// Nearest-neighbor scale-to-fit `src` (`src_w x src_h` interleaved RGB)
+/// into a `dst_w x dst_h` black canvas, centered - the same letterbox
+/// behavior `VideoDecoder::read_frame` gets from OpenCV's `imgproc::resize`
+/// plus a centered ROI copy, implemented by hand since there's no OpenCV
+/// here to do the resize.
+fn letterbox_rgb(src: &[u8], src_w: usize, src_h: usize, dst_w: usize, dst_h: usize) -> Vec<u8> {
+    let scale = (dst_w as f64 / src_w as f64).min(dst_h as f64 / src_h as f64);
+    let new_w = ((src_w as f64 * scale).round() as usize).max(1);
+    let new_h = ((src_h as f64 * scale).round() as usize).max(1);
+
+    let x_off = (dst_w.saturating_sub(new_w)) / 2;
+    let y_off = (dst_h.saturating_sub(new_h)) / 2;
+
+    let mut canvas = vec![0u8; dst_w * dst_h * 3];
+    for y in 0..new_h {
+        let src_y = ((y as f64 / scale) as usize).min(src_h - 1);
+        for x in 0..new_w {
+            let src_x = ((x as f64 / scale) as usize).min(src_w - 1);
+            let src_idx = (src_y * src_w + src_x) * 3;
+            let dst_idx = ((y + y_off) * dst_w + (x + x_off)) * 3;
+            canvas[dst_idx..dst_idx + 3].copy_from_slice(&src[src_idx..src_idx + 3]);
+        }
+    }
+
+    canvas
+}
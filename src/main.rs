@@ -1,5 +1,10 @@
 mod core;
 mod utils;
+// The alternate half-block renderer under `renderer/` was an earlier
+// rewrite attempt and isn't wired into the live playback path, but it's
+// kept compiling so it doesn't silently bit-rot if it's ever revived.
+#[allow(dead_code)]
+mod renderer;
 
 use clap::{Parser, Subcommand};
 use anyhow::{Result, Context};
@@ -7,11 +12,10 @@ use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 use std::thread;
-use crossterm::event::{self, Event, KeyCode};
 use serde_json::json;
 
 use crate::core::display_manager::{DisplayManager, DisplayMode};
-use crate::core::audio_manager::AudioManager;
+use crate::core::audio_manager::{AudioChannel, AudioManager};
 use crate::core::frame_manager::FrameManager;
 use crate::core::extractor;
 
@@ -47,6 +51,22 @@ enum Commands {
         fps: u32,
         #[arg(short, long, value_enum, default_value_t = DisplayMode::Rgb)]
         mode: DisplayMode,
+        #[arg(long, default_value = "stereo", help = "Which audio channel to play: stereo, left, right, mix, or a channel index")]
+        audio_channel: AudioChannel,
+        #[arg(long, value_enum, default_value_t = crate::core::display_manager::DitherMode::None, help = "Dither cells before rendering to smooth banding in Ascii/Ansi256 modes: none, ordered, or error-diffusion")]
+        dither: crate::core::display_manager::DitherMode,
+        #[arg(long, default_value_t = false, help = "Alternate scan direction each row when dithering")]
+        serpentine: bool,
+        #[arg(long, help = "Timed captions to overlay on the last rows of each frame: a .srt or .json sidecar file")]
+        captions: Option<String>,
+        #[arg(long, default_value_t = false, help = "Play with no audio, even if an audio track/file is given")]
+        mute: bool,
+        #[arg(long, default_value_t = 0.0, help = "Nudge A/V sync by this many seconds (positive holds video back for audio that is heard late, negative moves video earlier)")]
+        av_offset: f64,
+        #[arg(long, help = "Record the terminal byte stream to an asciicast v2 file, replayable with `play-cast`")]
+        record_cast: Option<String>,
+        #[arg(long, help = "Record the rendered cell grid to a QOI-style delta file, replayable with `play-cells`")]
+        record_cells: Option<String>,
     },
     /// Play video directly (real-time, no extraction)
     PlayLive {
@@ -64,6 +84,66 @@ enum Commands {
         mode: DisplayMode,
         #[arg(short, long, default_value_t = false, help = "If true, Fill mode: crop to fill 16:9 box (center crop)")]
         fill: bool,
+        #[arg(long, help = "Record the rendered output to a video file (.mp4 or .gif) while playing")]
+        record: Option<String>,
+        #[arg(long, default_value = "stereo", help = "Which audio channel to play: stereo, left, right, mix, or a channel index")]
+        audio_channel: AudioChannel,
+        #[arg(long, value_enum, default_value_t = crate::core::display_manager::DitherMode::None, help = "Dither cells before rendering to smooth banding in Ascii/Ansi256 modes: none, ordered, or error-diffusion")]
+        dither: crate::core::display_manager::DitherMode,
+        #[arg(long, default_value_t = false, help = "Alternate scan direction each row when dithering")]
+        serpentine: bool,
+        #[arg(long, help = "Start playback at this offset: seconds or MM:SS")]
+        start: Option<crate::core::player::TimeSpec>,
+        #[arg(long, help = "Stop playback at this offset: seconds or MM:SS")]
+        end: Option<crate::core::player::TimeSpec>,
+        #[arg(long, help = "Speed up a time range: START,END[,FACTOR] (e.g. 1:30,2:00,2.0); repeatable")]
+        fast: Vec<crate::core::player::FastRange>,
+        #[arg(long, help = "Timed captions to overlay on the last rows of each frame: a .srt or .json sidecar file")]
+        captions: Option<String>,
+        #[arg(long, default_value_t = false, help = "Play with no audio, even if an audio track/file is given")]
+        mute: bool,
+        #[arg(long, default_value_t = 0.0, help = "Nudge A/V sync by this many seconds (positive holds video back for audio that is heard late, negative moves video earlier)")]
+        av_offset: f64,
+        #[arg(long, help = "Record the terminal byte stream to an asciicast v2 file, replayable with `play-cast`")]
+        record_cast: Option<String>,
+        #[arg(long, value_enum, default_value_t = crate::core::video_decoder::DecodeBackend::Auto, help = "Video decode backend: auto, videotoolbox, mediafoundation, v4l2m2m, or software")]
+        backend: crate::core::video_decoder::DecodeBackend,
+        #[arg(long, help = "Record the rendered cell grid to a QOI-style delta file, replayable with `play-cells`")]
+        record_cells: Option<String>,
+        #[arg(long, default_value_t = false, help = "Read a raw YUV4MPEG2 stream from stdin instead of opening `video` with OpenCV, e.g. `ffmpeg -i in.mkv -f yuv4mpegpipe - | gascii play-live --stdin -v -`")]
+        stdin: bool,
+        #[arg(long, value_enum, default_value_t = crate::core::render_target::RenderTarget::HalfBlock, help = "Render via a pixel graphics protocol instead of the half-block cell grid: half-block, kitty, sixel, or auto (sniff the terminal)")]
+        render_target: crate::core::render_target::RenderTarget,
+    },
+    /// Replay a session previously captured with `--record-cast`, streaming
+    /// its raw terminal bytes back honoring the recorded timestamps.
+    PlayCast {
+        #[arg(short, long)]
+        cast: String,
+    },
+    /// Replay a session previously captured with `--record-cells`, streaming
+    /// its decoded cell grid back at a fixed rate (no video re-decode).
+    PlayCells {
+        #[arg(short, long)]
+        cells: String,
+        #[arg(short, long, default_value_t = 30, help = "Playback rate in frames per second")]
+        fps: u32,
+    },
+    /// Render a video to a single self-contained Bash script that replays
+    /// it with nothing but `echo`/`sleep` - no dependency on this binary.
+    Render {
+        #[arg(short, long)]
+        input: String,
+        #[arg(short, long)]
+        output: String,
+        #[arg(short, long, default_value_t = 80)]
+        width: u32,
+        #[arg(short, long, default_value_t = 24)]
+        height: u32,
+        #[arg(short, long, default_value_t = 24)]
+        fps: u32,
+        #[arg(short, long, value_enum, default_value_t = DisplayMode::Rgb)]
+        mode: DisplayMode,
     },
     /// Detect platform info
     Detect,
@@ -71,6 +151,13 @@ enum Commands {
     TerminalSize,
     /// Interactive Mode (Menu)
     Interactive,
+    /// Run a headless playback session from a TOML project file (see
+    /// `core::project`), reusing the same settings `Interactive` offers to
+    /// save after a session.
+    Project {
+        #[arg(short, long)]
+        file: String,
+    },
 }
 
 fn main() -> Result<()> {
@@ -80,11 +167,29 @@ fn main() -> Result<()> {
         Commands::Extract { input, output_dir, width, height, fps } => {
             extractor::extract_frames(input, output_dir, *width, *height, *fps)?;
         }
-        Commands::Play { frames_dir, audio, fps, mode } => {
-            play_animation(frames_dir, audio.as_deref(), *fps, *mode)?;
+        Commands::Play { frames_dir, audio, fps, mode, audio_channel, dither, serpentine, captions, mute, av_offset, record_cast, record_cells } => {
+            let overlay = load_overlay(captions.as_deref())?;
+            play_animation(
+                frames_dir, audio.as_deref(), *fps, *mode, *audio_channel, *dither, *serpentine, &overlay,
+                *mute, *av_offset, record_cast.as_deref(), record_cells.as_deref(),
+            )?;
+        }
+        Commands::PlayLive { video, audio, width, height, fps, mode, fill: _, record, audio_channel, dither, serpentine, start, end, fast, captions, mute, av_offset, record_cast, backend, record_cells, stdin, render_target } => {
+            let overlay = load_overlay(captions.as_deref())?;
+            crate::core::player::play_realtime(
+                video, audio.as_deref(), *width, *height, *fps, *mode, record.as_deref(), *audio_channel, *dither, *serpentine,
+                start.map(|t| t.0), end.map(|t| t.0), fast.clone(), overlay, *mute, *av_offset, record_cast.as_deref(), *backend,
+                record_cells.as_deref(), *stdin, *render_target,
+            )?;
         }
-        Commands::PlayLive { video, audio, width, height, fps, mode, fill } => {
-            crate::core::player::play_realtime(video, audio.as_deref(), *width, *height, *fps, *mode, *fill)?;
+        Commands::PlayCast { cast } => {
+            crate::core::asciicast::play_cast(cast)?;
+        }
+        Commands::PlayCells { cells, fps } => {
+            crate::core::replay::play_cells(cells, *fps)?;
+        }
+        Commands::Render { input, output, width, height, fps, mode } => {
+            extractor::render_script(input, output, *width, *height, *fps, *mode)?;
         }
         Commands::Detect => {
             let info = crate::utils::platform::PlatformInfo::detect()?;
@@ -103,6 +208,9 @@ fn main() -> Result<()> {
         Commands::Interactive => {
             crate::core::interactive::run_interactive_mode()?;
         }
+        Commands::Project { file } => {
+            crate::core::project::run_project(std::path::Path::new(file))?;
+        }
     }
 
     Ok(())
@@ -122,9 +230,33 @@ fn normalize_terminal_size(raw_cols: u16, raw_rows: u16) -> (u16, u16) {
     (raw_cols, raw_rows)
 }
 
-fn play_animation(frames_dir: &str, audio_path: Option<&str>, fps: u32, mode: DisplayMode) -> Result<()> {
+/// Load a `--captions` sidecar file into an `Overlay`, or an empty one if
+/// none was given.
+fn load_overlay(path: Option<&str>) -> Result<crate::core::overlay::Overlay> {
+    match path {
+        Some(path) => crate::core::overlay::Overlay::load(std::path::Path::new(path)),
+        None => Ok(crate::core::overlay::Overlay::default()),
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn play_animation(
+    frames_dir: &str,
+    audio_path: Option<&str>,
+    fps: u32,
+    mode: DisplayMode,
+    audio_channel: AudioChannel,
+    dither: crate::core::display_manager::DitherMode,
+    serpentine: bool,
+    overlay: &crate::core::overlay::Overlay,
+    mute: bool,
+    av_offset: f64,
+    record_cast: Option<&str>,
+    record_cells: Option<&str>,
+) -> Result<()> {
     // 1. Initialize Managers
-    let mut display = DisplayManager::new(mode)?;
+    let mut display = DisplayManager::new(mode)?.with_dither(dither).with_serpentine(serpentine)
+        .with_record_cast(record_cast)?.with_record_cells(record_cells);
     let mut frames = FrameManager::new();
     let audio = AudioManager::new()?;
 
@@ -138,16 +270,31 @@ fn play_animation(frames_dir: &str, audio_path: Option<&str>, fps: u32, mode: Di
     }
 
     // 3. Start Audio
-    if let Some(path) = audio_path {
-        audio.play(path)?;
+    if !mute {
+        if let Some(path) = audio_path {
+            audio.play(path, audio_channel)?;
+        }
     }
 
     // 4. Initialize Frame Processor (based on first frame header) and Playback Loop
     // We will infer width/height from the first frame header if possible
     let mut processor_opt: Option<crate::core::processor::FrameProcessor> = None;
-    let frame_duration = Duration::from_secs_f64(1.0 / fps as f64);
-    let start_time = Instant::now();
-    
+    let mut frame_duration = Duration::from_secs_f64(1.0 / fps as f64);
+    // `av_offset` shifts the wall-clock schedule itself: a positive offset
+    // (audio running late) pushes `start_time` later so frames wait that
+    // much longer to match, a negative offset pulls it earlier.
+    let mut start_time = if av_offset >= 0.0 {
+        Instant::now() + Duration::from_secs_f64(av_offset)
+    } else {
+        Instant::now() - Duration::from_secs_f64(-av_offset)
+    };
+    let total_frames = frames.frame_count() as i64;
+
+    // Transport control state, mirroring `core::player::play_realtime`'s.
+    let mut paused = false;
+    let mut speed: f64 = 1.0;
+    let mut last_frame: Option<(Vec<u8>, usize, usize)> = None;
+
     let running = Arc::new(AtomicBool::new(true));
     let r = running.clone();
 
@@ -155,9 +302,58 @@ fn play_animation(frames_dir: &str, audio_path: Option<&str>, fps: u32, mode: Di
         r.store(false, Ordering::SeqCst);
     }).context("Error registering Ctrl-C handler")?;
 
-    for i in 0..frames.frame_count() {
-        if !running.load(Ordering::SeqCst) {
-            break;
+    let mut i: i64 = 0;
+    while running.load(Ordering::SeqCst) && i < total_frames {
+        if let Some(control) = crate::core::player::poll_transport_control()? {
+            match control {
+                crate::core::player::PlayerControl::Quit => break,
+                crate::core::player::PlayerControl::TogglePause => {
+                    paused = !paused;
+                    if paused {
+                        audio.pause();
+                    } else {
+                        audio.resume();
+                        start_time = Instant::now() - Duration::from_secs_f64((i as f64 / fps as f64) / speed);
+                    }
+                }
+                crate::core::player::PlayerControl::Seek(delta) => {
+                    let current = i as f64 / fps as f64;
+                    let target = (current + delta).max(0.0);
+                    i = (target * fps as f64).round() as i64;
+                    i = i.clamp(0, total_frames - 1);
+                    start_time = Instant::now() - Duration::from_secs_f64(target / speed);
+                    audio.seek(target);
+                }
+                crate::core::player::PlayerControl::StepFrame(delta) if paused => {
+                    i = (i + delta).clamp(0, total_frames - 1);
+                    audio.seek(i as f64 / fps as f64);
+                    render_animation_frame(&frames, &mut processor_opt, overlay, &mut display, fps, i, &mut last_frame)?;
+                }
+                crate::core::player::PlayerControl::StepFrame(_) => {} // no-op while playing
+                crate::core::player::PlayerControl::SpeedDown => {
+                    speed = (speed * 0.5).max(0.25);
+                    frame_duration = Duration::from_secs_f64(1.0 / (fps as f64 * speed));
+                    start_time = Instant::now() - Duration::from_secs_f64((i as f64 / fps as f64) / speed);
+                }
+                crate::core::player::PlayerControl::SpeedUp => {
+                    speed = (speed * 2.0).min(4.0);
+                    frame_duration = Duration::from_secs_f64(1.0 / (fps as f64 * speed));
+                    start_time = Instant::now() - Duration::from_secs_f64((i as f64 / fps as f64) / speed);
+                }
+                crate::core::player::PlayerControl::ToggleOsd => {} // no OSD in this player
+                crate::core::player::PlayerControl::Screenshot => {
+                    if let Some((rgb, w, h)) = last_frame.as_ref() {
+                        if let Err(e) = crate::core::player::write_screenshot(rgb, *w as u32, *h as u32) {
+                            eprintln!("⚠️  Screenshot failed: {}", e);
+                        }
+                    }
+                }
+            }
+        }
+
+        if paused {
+            thread::sleep(Duration::from_millis(50));
+            continue;
         }
 
         // Sync
@@ -167,38 +363,55 @@ fn play_animation(frames_dir: &str, audio_path: Option<&str>, fps: u32, mode: Di
         if elapsed < expected_time {
             thread::sleep(expected_time - elapsed);
         } else if elapsed > expected_time + Duration::from_millis(50) {
+            i += 1;
             continue; // Skip frame
         }
 
-        // Render
-        if let Some(frame_data_arc) = frames.get_frame(i) {
-            // frame_data is [width(u16)][height(u16)][R,G,B...]
-            let frame_slice = frame_data_arc.as_slice();
-            if frame_slice.len() >= 4 {
-                let w = u16::from_le_bytes([frame_slice[0], frame_slice[1]]) as usize;
-                let h = u16::from_le_bytes([frame_slice[2], frame_slice[3]]) as usize;
-                let pixel_data = &frame_slice[4..];
-
-                // Initialize processor if not set
-                if processor_opt.is_none() {
-                    processor_opt = Some(crate::core::processor::FrameProcessor::new(w, h));
-                }
+        render_animation_frame(&frames, &mut processor_opt, overlay, &mut display, fps, i, &mut last_frame)?;
+        i += 1;
+    }
 
-                if let Some(processor) = processor_opt.as_ref() {
-                    let cells = processor.process_frame(pixel_data);
-                    display.render_diff(&cells, w)?;
-                }
-            }
-        }
+    display.finish_recording()?;
 
-        // Input
-        if event::poll(Duration::from_millis(0))? {
-            if let Event::Key(key) = event::read()? {
-                if key.code == KeyCode::Char('q') {
-                    break;
-                }
-            }
-        }
+    Ok(())
+}
+
+/// Render frame `i` from `frames` through `processor` (lazily created from
+/// the first frame's header) and `overlay`, diffed onto the terminal via
+/// `display`. Shared by the normal playback tick and manual frame-stepping
+/// so both paths stay in sync on what "render this frame" means, including
+/// caching the raw RGB bytes in `last_frame` for `s`/screenshot.
+fn render_animation_frame(
+    frames: &FrameManager,
+    processor_opt: &mut Option<crate::core::processor::FrameProcessor>,
+    overlay: &crate::core::overlay::Overlay,
+    display: &mut DisplayManager,
+    fps: u32,
+    i: i64,
+    last_frame: &mut Option<(Vec<u8>, usize, usize)>,
+) -> Result<()> {
+    // frame_data is [width(u16)][height(u16)][R,G,B...]
+    let Some(frame_data_arc) = frames.get_frame(i as usize) else {
+        return Ok(());
+    };
+    let frame_slice = frame_data_arc.as_slice();
+    if frame_slice.len() < 4 {
+        return Ok(());
+    }
+
+    let w = u16::from_le_bytes([frame_slice[0], frame_slice[1]]) as usize;
+    let h = u16::from_le_bytes([frame_slice[2], frame_slice[3]]) as usize;
+    let pixel_data = &frame_slice[4..];
+
+    if processor_opt.is_none() {
+        *processor_opt = Some(crate::core::processor::FrameProcessor::new(w, h));
+    }
+
+    if let Some(processor) = processor_opt.as_ref() {
+        let mut cells = processor.process_frame(pixel_data);
+        overlay.apply(&mut cells, w, i as f64 / fps as f64);
+        display.render_diff(&cells, w)?;
+        *last_frame = Some((pixel_data.to_vec(), w, h));
     }
 
     Ok(())
@@ -1,12 +1,27 @@
 /// ANSI 256-color palette quantizer
-/// 
+///
 /// Converts RGB colors to ANSI 256-color indices for terminal output.
-/// This reduces data size from 3 bytes (RGB) to 1 byte (index), 
+/// This reduces data size from 3 bytes (RGB) to 1 byte (index),
 /// achieving 66% data reduction.
 
 use std::sync::OnceLock;
 
+// Quantizing against the true nearest palette entry (by Euclidean RGB
+// distance) instead of the old 6x6x6-cube heuristic fixes visible color
+// errors in mid-tones, but a full 16.7M-entry x 256-candidate search is too
+// slow to build at startup. Keying the LUT on 5 bits per channel (32^3 =
+// 32768 entries, looked up by shifting the input right by 3) keeps the
+// build to ~8M distance computations and the table itself to 32KB.
+const LUT_BITS: u32 = 5;
+const LUT_DIM: usize = 1 << LUT_BITS; // 32
+const LUT_SHIFT: u32 = 8 - LUT_BITS; // 3
+
 static COLOR_LUT: OnceLock<Vec<u8>> = OnceLock::new();
+static PALETTE_RGB: OnceLock<[(u8, u8, u8); 256]> = OnceLock::new();
+// Set GASCII_LUMA_WEIGHT=1 to weight green error more heavily when picking
+// the nearest palette entry, matching how luma (0.299R+0.587G+0.114B)
+// weights human brightness perception toward green.
+static LUMA_WEIGHTED: OnceLock<bool> = OnceLock::new();
 
 pub struct ColorQuantizer;
 
@@ -14,64 +29,94 @@ impl ColorQuantizer {
     /// Quantize RGB color to nearest ANSI 256-color index
     pub fn quantize_rgb(r: u8, g: u8, b: u8) -> u8 {
         // Get or initialize LUT
-        let lut = COLOR_LUT.get_or_init(|| Self::build_lut());
-        
-        // Lookup in pre-computed table
-        let idx = ((r as usize) << 16) | ((g as usize) << 8) | (b as usize);
+        let lut = COLOR_LUT.get_or_init(Self::build_lut);
+
+        // Lookup in the reduced-precision table
+        let idx = ((r as usize >> LUT_SHIFT) << (2 * LUT_BITS))
+            | ((g as usize >> LUT_SHIFT) << LUT_BITS)
+            | (b as usize >> LUT_SHIFT);
         lut[idx]
     }
-    
-    /// Build look-up table mapping RGB to ANSI 256 colors
-    fn build_lut() -> Vec<u8> {
-        // Allocate on heap directly to avoid stack overflow (16MB)
-        let mut lut = vec![0u8; 256 * 256 * 256];
-        
-        for r in 0..256 {
-            for g in 0..256 {
-                for b in 0..256 {
-                    let idx = (r << 16) | (g << 8) | b;
-                    lut[idx] = Self::rgb_to_ansi256(r as u8, g as u8, b as u8);
-                }
+
+    fn luma_weighted() -> bool {
+        *LUMA_WEIGHTED.get_or_init(|| {
+            std::env::var("GASCII_LUMA_WEIGHT")
+                .map(|v| v == "1" || v.eq_ignore_ascii_case("true"))
+                .unwrap_or(false)
+        })
+    }
+
+    /// Squared color distance, optionally weighting the green channel more
+    /// heavily to better approximate perceived luma difference.
+    fn color_distance_sq(a: (u8, u8, u8), b: (u8, u8, u8), weighted: bool) -> i32 {
+        let dr = a.0 as i32 - b.0 as i32;
+        let dg = a.1 as i32 - b.1 as i32;
+        let db = a.2 as i32 - b.2 as i32;
+        if weighted {
+            dr * dr + 2 * dg * dg + db * db
+        } else {
+            dr * dr + dg * dg + db * db
+        }
+    }
+
+    /// The actual RGB value of every ANSI 256 palette entry, computed once.
+    fn palette() -> &'static [(u8, u8, u8); 256] {
+        PALETTE_RGB.get_or_init(|| {
+            let mut palette = [(0u8, 0u8, 0u8); 256];
+            for (i, entry) in palette.iter_mut().enumerate() {
+                *entry = Self::ansi256_to_rgb(i as u8);
+            }
+            palette
+        })
+    }
+
+    /// Find the true nearest palette entry to `rgb` by Euclidean distance.
+    ///
+    /// Only searches the algorithmic color cube + grayscale ramp (16-255),
+    /// not the standard 16 colors (0-15): those are typically remapped by
+    /// the terminal's own theme, so `ansi256_to_rgb`'s hardcoded approximation
+    /// for them isn't a reliable distance target.
+    fn nearest_palette_index(rgb: (u8, u8, u8)) -> u8 {
+        let weighted = Self::luma_weighted();
+        let palette = Self::palette();
+
+        let mut best_idx = 16u8;
+        let mut best_dist = i32::MAX;
+        for (i, &candidate) in palette.iter().enumerate().skip(16) {
+            let dist = Self::color_distance_sq(rgb, candidate, weighted);
+            if dist < best_dist {
+                best_dist = dist;
+                best_idx = i as u8;
             }
         }
-        
-        lut
+        best_idx
     }
-    
-    /// Convert RGB to ANSI 256 color index
-    /// 
-    /// ANSI 256 color palette:
-    /// - 0-15: Standard colors
-    /// - 16-231: 6×6×6 color cube
-    /// - 232-255: Grayscale
-    fn rgb_to_ansi256(r: u8, g: u8, b: u8) -> u8 {
-        // Check if grayscale
-        let gray_threshold = 8;
-        if (r as i16 - g as i16).abs() < gray_threshold
-            && (r as i16 - b as i16).abs() < gray_threshold
-            && (g as i16 - b as i16).abs() < gray_threshold
-        {
-            // Grays: 232-255 (24 shades)
-            let gray = ((r as u16 + g as u16 + b as u16) / 3) as u8;
-            if gray < 8 {
-                return 16; // Black from color cube
-            } else if gray > 238 {
-                return 231; // White from color cube
-            } else {
-                return 232 + ((gray - 8) * 24 / 230);
+
+    /// Build the reduced-precision (5 bits/channel) nearest-palette LUT.
+    fn build_lut() -> Vec<u8> {
+        let mut lut = vec![0u8; LUT_DIM * LUT_DIM * LUT_DIM];
+
+        for r5 in 0..LUT_DIM {
+            for g5 in 0..LUT_DIM {
+                for b5 in 0..LUT_DIM {
+                    // Reconstruct a representative 8-bit value at the center
+                    // of this bucket rather than its low edge.
+                    let r = ((r5 << LUT_SHIFT) | (1 << (LUT_SHIFT - 1))) as u8;
+                    let g = ((g5 << LUT_SHIFT) | (1 << (LUT_SHIFT - 1))) as u8;
+                    let b = ((b5 << LUT_SHIFT) | (1 << (LUT_SHIFT - 1))) as u8;
+
+                    let idx = (r5 << (2 * LUT_BITS)) | (g5 << LUT_BITS) | b5;
+                    lut[idx] = Self::nearest_palette_index((r, g, b));
+                }
             }
         }
-        
-        // Map to 6×6×6 color cube (16-231)
-        let r6 = (r as u16 * 6 / 256) as u8;
-        let g6 = (g as u16 * 6 / 256) as u8;
-        let b6 = (b as u16 * 6 / 256) as u8;
-        
-        16 + 36 * r6 + 6 * g6 + b6
+
+        lut
     }
-    
-    /// Get RGB values for an ANSI 256 color index (for testing)
-    #[allow(dead_code)]
+
+    /// Get the approximate RGB values for an ANSI 256 color index. Used by
+    /// `palette()` to precompute the distance targets for the nearest-entry
+    /// search.
     pub fn ansi256_to_rgb(index: u8) -> (u8, u8, u8) {
         match index {
             // Standard 16 colors (approximations)